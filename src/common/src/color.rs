@@ -0,0 +1,94 @@
+//! Color space conversions used by RGB effects
+
+use crate::types::RgbColor;
+
+/// Map a temperature into a green-to-red gradient for `RgbEffect::Temperature`.
+///
+/// `t` is clamped into the `[t_cold, t_hot]` band, normalized to `f` in
+/// `[0, 1]`, and used to interpolate hue linearly from 120° (green) at
+/// `t_cold` down to 0° (red) at `t_hot`, then converted from HSV(hue, 1, 1).
+pub fn temperature_to_color(t: f32, t_cold: f32, t_hot: f32) -> RgbColor {
+    let (lo, hi) = (t_cold.min(t_hot), t_cold.max(t_hot));
+    let t = t.clamp(lo, hi);
+    let f = if (t_hot - t_cold).abs() < f32::EPSILON {
+        0.0
+    } else {
+        ((t - t_cold) / (t_hot - t_cold)).clamp(0.0, 1.0)
+    };
+    let hue = 120.0 * (1.0 - f);
+    hsv_to_rgb(hue, 1.0, 1.0)
+}
+
+/// Interpolate a color along an ordered list of `(temperature, color)` stops.
+///
+/// `temp` is clamped to the first/last stop below/above the gradient's range.
+/// Between two bracketing stops the color channels are interpolated with
+/// integer math (no floating-point division), so a steady input temperature
+/// always produces the exact same output color instead of drifting by a
+/// rounding unit between polls.
+pub fn interpolate_gradient(stops: &[(u8, RgbColor)], temp: f32) -> RgbColor {
+    let Some((&(first_temp, first_color), &(last_temp, last_color))) =
+        stops.first().zip(stops.last())
+    else {
+        return RgbColor::default();
+    };
+
+    let t = temp.round() as i32;
+    if t <= first_temp as i32 {
+        return first_color;
+    }
+    if t >= last_temp as i32 {
+        return last_color;
+    }
+
+    for window in stops.windows(2) {
+        let (t0, c0) = window[0];
+        let (t1, c1) = window[1];
+        if t > t1 as i32 {
+            continue;
+        }
+        let span = (t1 as i32 - t0 as i32).max(1);
+        let frac = t - t0 as i32;
+        let lerp = |a: u8, b: u8| -> u8 { (a as i32 + (b as i32 - a as i32) * frac / span) as u8 };
+        return RgbColor::new(lerp(c0.r, c1.r), lerp(c0.g, c1.g), lerp(c0.b, c1.b));
+    }
+
+    last_color
+}
+
+/// Linearly interpolate each RGB channel between `cool` (at `t_min`) and
+/// `hot` (at `t_max`) for `RgbEffect::Temperature` when no `temp_gradient`
+/// is configured. `t` is clamped into `[t_min, t_max]` before the `[0, 1]`
+/// fraction `f` is computed, so callers don't need to clamp beforehand.
+pub fn interpolate_linear(cool: RgbColor, hot: RgbColor, t: f32, t_min: f32, t_max: f32) -> RgbColor {
+    let (lo, hi) = (t_min.min(t_max), t_min.max(t_max));
+    let t = t.clamp(lo, hi);
+    let f = if (t_max - t_min).abs() < f32::EPSILON {
+        0.0
+    } else {
+        ((t - t_min) / (t_max - t_min)).clamp(0.0, 1.0)
+    };
+    let lerp = |a: u8, b: u8| -> u8 { (a as f32 + f * (b as f32 - a as f32)).round() as u8 };
+    RgbColor::new(lerp(cool.r, hot.r), lerp(cool.g, hot.g), lerp(cool.b, hot.b))
+}
+
+/// Convert HSV (hue in degrees `[0, 360)`, saturation/value in `[0, 1]`) to 8-bit RGB
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> RgbColor {
+    let c = value * saturation;
+    let h_prime = (hue / 60.0) % 6.0;
+    let x = c * (1.0 - (h_prime % 2.0 - 1.0).abs());
+    let (r1, g1, b1) = match h_prime as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = value - c;
+    RgbColor::new(
+        ((r1 + m) * 255.0).round() as u8,
+        ((g1 + m) * 255.0).round() as u8,
+        ((b1 + m) * 255.0).round() as u8,
+    )
+}
@@ -6,6 +6,12 @@
 pub mod types;
 pub mod error;
 pub mod dbus_interface;
+pub mod limits;
+pub mod color;
+pub mod palette;
 
 pub use types::*;
 pub use error::*;
+pub use limits::{limits_for_model, RangeLimit, SettingsLimits};
+pub use color::{interpolate_gradient, interpolate_linear, temperature_to_color};
+pub use palette::{builtin_palettes, palette_by_name, RgbPalette};
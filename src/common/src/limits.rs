@@ -0,0 +1,92 @@
+//! Per-model hardware setting limits
+//!
+//! Safe battery, fan, and TDP ranges vary by model (EC firmware, power
+//! delivery, thermal design), so rather than hardcoding one set of bounds we
+//! ship a small embedded table keyed by `model_name`, with a generic
+//! `"default"` fallback for anything unrecognized.
+
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+/// Raw embedded table; parsed once and cached behind `model_limits_table()`
+const MODEL_LIMITS_JSON: &str = include_str!("../assets/model_limits.json");
+
+/// An inclusive range with a step, e.g. a battery limit in whole percent
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RangeLimit {
+    pub min: u32,
+    pub max: u32,
+    pub step: u32,
+}
+
+impl RangeLimit {
+    /// Whether `value` falls within range and lands on a valid step
+    pub fn contains(&self, value: u32) -> bool {
+        value >= self.min && value <= self.max && (value - self.min) % self.step.max(1) == 0
+    }
+
+    /// Clamp `value` into range and round it to the nearest step
+    pub fn clamp(&self, value: u32) -> u32 {
+        let clamped = value.clamp(self.min, self.max);
+        let step = self.step.max(1);
+        let steps = ((clamped - self.min) as f64 / step as f64).round() as u32;
+        (self.min + steps * step).min(self.max)
+    }
+}
+
+/// Per-model setting boundaries, selected by `model_name` via [`limits_for_model`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SettingsLimits {
+    pub model_name: String,
+    /// Discrete battery charge-limit presets offered by the GUI; `set_battery_limit`
+    /// itself validates against `battery_range`
+    pub battery_thresholds: Vec<u8>,
+    /// Full range accepted by the fine-grained battery threshold API
+    pub battery_range: RangeLimit,
+    pub fan_duty: RangeLimit,
+    pub fan_temp: RangeLimit,
+    /// `None` when no TDP backend was detected on this system (see
+    /// `HardwareController::detect_capabilities`), regardless of what the
+    /// model table says, since a model entry doesn't imply the control is
+    /// actually reachable (e.g. an Intel machine with no ryzenadj backend)
+    pub tdp_spl: Option<RangeLimit>,
+    pub tdp_sppt: Option<RangeLimit>,
+    pub tdp_fppt: Option<RangeLimit>,
+    /// RGB keyboard brightness range (0-100 on every known model)
+    pub rgb_brightness: RangeLimit,
+    /// RGB effect speed range (0-100 on every known model)
+    pub rgb_speed: RangeLimit,
+    /// Whether this model exposes independently-addressable per-key RGB
+    /// zones rather than a single whole-keyboard zone
+    pub per_key_rgb: bool,
+}
+
+fn model_limits_table() -> &'static Vec<SettingsLimits> {
+    static TABLE: OnceLock<Vec<SettingsLimits>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        serde_json::from_str(MODEL_LIMITS_JSON)
+            .expect("embedded model_limits.json must parse")
+    })
+}
+
+fn default_limits() -> SettingsLimits {
+    model_limits_table()
+        .iter()
+        .find(|l| l.model_name == "default")
+        .cloned()
+        .expect("embedded model_limits.json must contain a \"default\" entry")
+}
+
+/// Look up the setting limits for a detected model, falling back to the
+/// generic `"default"` entry when the model is unknown or undetected
+pub fn limits_for_model(model_name: Option<&str>) -> SettingsLimits {
+    let Some(model_name) = model_name else {
+        return default_limits();
+    };
+
+    model_limits_table()
+        .iter()
+        .find(|l| l.model_name.eq_ignore_ascii_case(model_name))
+        .cloned()
+        .unwrap_or_else(default_limits)
+}
@@ -0,0 +1,49 @@
+//! Built-in RGB lighting palettes
+//!
+//! A palette is a small named set of curated colors users can apply in one
+//! click instead of picking primary/secondary colors individually.
+
+use crate::types::RgbColor;
+use serde::{Deserialize, Serialize};
+
+/// A named set of colors for `RgbEffect` presets and per-key distribution
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RgbPalette {
+    pub name: String,
+    pub colors: Vec<RgbColor>,
+}
+
+impl RgbPalette {
+    fn new(name: &str, colors: &[(u8, u8, u8)]) -> Self {
+        Self {
+            name: name.to_string(),
+            colors: colors.iter().map(|&(r, g, b)| RgbColor::new(r, g, b)).collect(),
+        }
+    }
+}
+
+/// All built-in palettes, in display order
+pub fn builtin_palettes() -> Vec<RgbPalette> {
+    vec![
+        RgbPalette::new(
+            "Nord",
+            &[(46, 52, 64), (59, 66, 82), (136, 192, 208), (163, 190, 140)],
+        ),
+        RgbPalette::new(
+            "Sunset",
+            &[(255, 94, 77), (255, 154, 0), (237, 65, 149), (112, 0, 120)],
+        ),
+        RgbPalette::new(
+            "Ocean",
+            &[(0, 119, 182), (0, 180, 216), (144, 224, 239), (3, 4, 94)],
+        ),
+        RgbPalette::new("Mono", &[(255, 255, 255), (200, 200, 200), (120, 120, 120)]),
+    ]
+}
+
+/// Look up a built-in palette by name (case-insensitive)
+pub fn palette_by_name(name: &str) -> Option<RgbPalette> {
+    builtin_palettes()
+        .into_iter()
+        .find(|p| p.name.eq_ignore_ascii_case(name))
+}
@@ -1,5 +1,6 @@
 //! Common data types for ASUS Armoury Crate Linux
 
+use crate::error::{ArmouryError, ArmouryResult};
 use serde::{Deserialize, Serialize};
 
 /// CPU Performance modes available on ASUS laptops
@@ -63,7 +64,7 @@ pub enum FanMode {
 }
 
 /// A point in a fan curve (temperature -> fan percentage)
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FanCurvePoint {
     /// Temperature in Celsius
     pub temperature: u8,
@@ -72,7 +73,7 @@ pub struct FanCurvePoint {
 }
 
 /// Fan curve definition with multiple temperature/speed points
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FanCurve {
     /// Name of the fan curve profile
     pub name: String,
@@ -97,6 +98,74 @@ impl Default for FanCurve {
     }
 }
 
+impl FanCurve {
+    /// Reject curves that can't be programmed sanely: no points, duty
+    /// outside 0-100, or temperatures that aren't strictly increasing
+    pub fn validate(&self) -> ArmouryResult<()> {
+        if self.points.is_empty() {
+            return Err(ArmouryError::InvalidValue(format!(
+                "Fan curve \"{}\" has no points",
+                self.name
+            )));
+        }
+
+        for point in &self.points {
+            if point.fan_percent > 100 {
+                return Err(ArmouryError::InvalidValue(format!(
+                    "Fan curve \"{}\" has duty {}% out of range (0-100)",
+                    self.name, point.fan_percent
+                )));
+            }
+        }
+
+        for window in self.points.windows(2) {
+            if window[1].temperature <= window[0].temperature {
+                return Err(ArmouryError::InvalidValue(format!(
+                    "Fan curve \"{}\" points must be strictly increasing in temperature ({} then {})",
+                    self.name, window[0].temperature, window[1].temperature
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Linearly interpolate duty percent at `temp`, with flat extrapolation
+    /// below the first point and above the last
+    pub fn duty_at(&self, temp: f32) -> u8 {
+        interpolate_fan_points(&self.points, temp)
+    }
+}
+
+/// Linearly interpolate fan duty at `temp` between the bracketing points in
+/// `points`, with flat extrapolation below the first point and above the last
+pub fn interpolate_fan_points(points: &[FanCurvePoint], temp: f32) -> u8 {
+    let Some((first, last)) = points.first().zip(points.last()) else {
+        return 0;
+    };
+    if temp <= first.temperature as f32 {
+        return first.fan_percent;
+    }
+    if temp >= last.temperature as f32 {
+        return last.fan_percent;
+    }
+
+    for window in points.windows(2) {
+        let (lo, hi) = (window[0], window[1]);
+        if temp >= lo.temperature as f32 && temp <= hi.temperature as f32 {
+            let span = hi.temperature as f32 - lo.temperature as f32;
+            if span <= 0.0 {
+                return lo.fan_percent;
+            }
+            let f = (temp - lo.temperature as f32) / span;
+            let value = lo.fan_percent as f32 + f * (hi.fan_percent as f32 - lo.fan_percent as f32);
+            return value.round() as u8;
+        }
+    }
+
+    last.fan_percent
+}
+
 /// RGB lighting effects
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum RgbEffect {
@@ -113,6 +182,8 @@ pub enum RgbEffect {
     Spectrum,
     /// Reactive/keypress effect
     Reactive,
+    /// Color derived live from a temperature sensor, see [`temperature_to_color`](crate::temperature_to_color)
+    Temperature,
     /// Off
     Off,
 }
@@ -126,13 +197,14 @@ impl std::fmt::Display for RgbEffect {
             Self::Wave => write!(f, "Wave"),
             Self::Spectrum => write!(f, "Spectrum"),
             Self::Reactive => write!(f, "Reactive"),
+            Self::Temperature => write!(f, "Temperature"),
             Self::Off => write!(f, "Off"),
         }
     }
 }
 
 /// RGB color value
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub struct RgbColor {
     pub r: u8,
     pub g: u8,
@@ -160,8 +232,17 @@ impl RgbColor {
     }
 }
 
+/// Sensor driving the `RgbEffect::Temperature` gradient
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TempSensor {
+    Cpu,
+    Gpu,
+    /// The hotter of CPU and GPU on each poll
+    Max,
+}
+
 /// RGB keyboard settings
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RgbSettings {
     /// Current effect
     pub effect: RgbEffect,
@@ -173,6 +254,43 @@ pub struct RgbSettings {
     pub brightness: u8,
     /// Effect speed (0-100)
     pub speed: u8,
+    /// Sensor sampled for `RgbEffect::Temperature`; defaults to CPU when unset
+    pub temp_sensor: Option<TempSensor>,
+    /// (cold, hot) anchor temperatures in Celsius for `RgbEffect::Temperature`;
+    /// defaults to (40, 90), roughly this model's idle-to-throttle range
+    pub temp_band: Option<(u8, u8)>,
+    /// Ordered `(temperature °C, color)` gradient stops for `RgbEffect::Temperature`.
+    /// When set, this takes precedence over both `temp_band` and the plain
+    /// `color`/`color_secondary` linear interpolation; the daemon's
+    /// temperature-RGB loop picks whichever of the two is configured
+    pub temp_gradient: Option<Vec<(u8, RgbColor)>>,
+    /// Per-zone colors, indexed by zone id, for keyboards with
+    /// `HardwareCapabilities::per_key_rgb`. `None` applies `color` to the
+    /// whole keyboard as a single zone
+    pub zone_colors: Option<Vec<RgbColor>>,
+    /// How often the daemon's `RgbEffect::Temperature` loop re-samples the
+    /// sensor and recomputes the color, in milliseconds. `None` falls back to
+    /// the daemon's own `poll_interval_ms`
+    pub temp_poll_interval_ms: Option<u32>,
+}
+
+/// Backend used to drive the RGB keyboard, when more than one is available
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum RgbBackend {
+    /// The sysfs LED class / `asusctl` path
+    #[default]
+    Asusctl,
+    /// Talk to the Aura USB HID controller directly
+    NativeHid,
+}
+
+impl std::fmt::Display for RgbBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Asusctl => write!(f, "asusctl/sysfs"),
+            Self::NativeHid => write!(f, "native Aura HID"),
+        }
+    }
 }
 
 impl Default for RgbSettings {
@@ -183,21 +301,32 @@ impl Default for RgbSettings {
             color_secondary: None,
             brightness: 100,
             speed: 50,
+            temp_sensor: None,
+            temp_band: None,
+            temp_gradient: None,
+            zone_colors: None,
+            temp_poll_interval_ms: None,
         }
     }
 }
 
-/// Battery charge limit settings
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+/// Battery charge control settings
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct BatterySettings {
-    /// Maximum charge limit percentage (60, 80, or 100)
-    pub charge_limit: u8,
+    /// Resume charging below this percentage, on hardware with a start threshold
+    pub charge_control_start_threshold: u8,
+    /// Stop charging once the battery reaches this percentage
+    pub charge_control_end_threshold: u8,
+    /// Input current / charge-rate cap in mA, where the hardware exposes one
+    pub charge_rate_ma: Option<u32>,
 }
 
 impl Default for BatterySettings {
     fn default() -> Self {
         Self {
-            charge_limit: 100,
+            charge_control_start_threshold: 0,
+            charge_control_end_threshold: 100,
+            charge_rate_ma: None,
         }
     }
 }
@@ -225,9 +354,49 @@ pub struct SystemStatus {
     pub power_draw: f32,
 }
 
+/// One sampled row of telemetry from the sensor monitor, as logged to CSV
+/// and returned by the `monitoring_snapshot` D-Bus method
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonitorSample {
+    /// Milliseconds since the Unix epoch
+    pub timestamp_unix_ms: u128,
+    pub cpu_temp: f32,
+    pub gpu_temp: f32,
+    pub cpu_usage: f32,
+    pub gpu_usage: f32,
+    pub cpu_fan_rpm: u32,
+    pub gpu_fan_rpm: u32,
+    pub battery_percent: u8,
+    pub ac_connected: bool,
+    pub power_draw: f32,
+    /// Name of the profile active when this sample was taken
+    pub profile: String,
+}
+
+/// Current on-disk shape of [`Profile`]; bumped whenever a field is added or
+/// changed in a way `load_profiles`'s migration step needs to backfill for
+/// older files (see `ProfileManager::migrate_profile_value`)
+pub const CURRENT_PROFILE_SCHEMA_VERSION: u32 = 1;
+
+/// Summary of a `ProfileManager::new` load pass, so the daemon and GUI can
+/// surface parse failures instead of the profiles silently vanishing
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProfileLoadSummary {
+    /// Profiles that parsed as the current schema with no migration needed
+    pub loaded: u32,
+    /// Profiles parsed from an older schema and upgraded, then re-saved
+    pub migrated: u32,
+    /// Profiles that failed to parse and were moved to the `.corrupt` subdirectory
+    pub quarantined: u32,
+}
+
 /// User profile containing all settings
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Profile {
+    /// On-disk schema version; defaults to 0 (pre-versioning) for files that
+    /// predate this field, which `load_profiles` treats as needing migration
+    #[serde(default)]
+    pub version: u32,
     /// Profile name
     pub name: String,
     /// Performance mode
@@ -242,11 +411,65 @@ pub struct Profile {
     pub rgb_settings: RgbSettings,
     /// Battery settings
     pub battery_settings: BatterySettings,
+    /// Pinned TDP (power-limit) values; `None` leaves the current limits untouched
+    pub tdp_settings: Option<TdpSettings>,
+    /// Named variants of this profile (e.g. "Gaming / AC" vs "Gaming / Battery"),
+    /// each a self-contained set of concrete settings
+    pub variants: Vec<ProfileVariant>,
+    /// Variant applied whenever this profile is selected; falls back to the
+    /// first entry in `variants` if unset or no longer present
+    pub default_variant_id: Option<u64>,
+}
+
+/// Identifying metadata for a `ProfileVariant`, returned by `list_variants`
+/// without the full settings payload
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariantInfo {
+    pub id: u64,
+    pub name: String,
+}
+
+/// A concrete set of settings a profile can switch to, e.g. to adapt to AC
+/// vs battery power without creating a whole separate profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileVariant {
+    /// Id and display name of this variant
+    pub info: VariantInfo,
+    pub performance_mode: PerformanceMode,
+    pub gpu_mode: GpuMode,
+    pub fan_mode: FanMode,
+    pub fan_curve: Option<FanCurve>,
+    pub rgb_settings: RgbSettings,
+    pub battery_settings: BatterySettings,
+    pub tdp_settings: Option<TdpSettings>,
+}
+
+/// A condition the auto-switch policy engine can match against, pairing
+/// with a profile name in an [`AutoSwitchRule`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AutoSwitchCondition {
+    /// Matches whenever the laptop is running on AC power
+    OnAcPower,
+    /// Matches whenever the laptop is running on battery power
+    OnBatteryPower,
+    /// Matches whenever any process whose name contains one of these
+    /// (case-insensitive) is currently running
+    ProcessRunning(Vec<String>),
+}
+
+/// One rule in the auto-switch policy engine: when `condition` matches,
+/// `profile_name` becomes the active profile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutoSwitchRule {
+    pub id: u64,
+    pub condition: AutoSwitchCondition,
+    pub profile_name: String,
 }
 
 impl Default for Profile {
     fn default() -> Self {
         Self {
+            version: CURRENT_PROFILE_SCHEMA_VERSION,
             name: "Default".to_string(),
             performance_mode: PerformanceMode::Balanced,
             gpu_mode: GpuMode::Hybrid,
@@ -254,15 +477,58 @@ impl Default for Profile {
             fan_curve: None,
             rgb_settings: RgbSettings::default(),
             battery_settings: BatterySettings::default(),
+            tdp_settings: None,
+            variants: Vec::new(),
+            default_variant_id: None,
         }
     }
 }
 
+/// Backend used to apply TDP (power-limit) settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TdpBackend {
+    /// ASUS WMI firmware attributes (ppt_pl1_spl / ppt_pl2_sppt / ppt_fppt)
+    AsusWmi,
+    /// `ryzenadj` process invocation (AMD-only fallback)
+    RyzenAdj,
+}
+
+impl std::fmt::Display for TdpBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AsusWmi => write!(f, "ASUS WMI"),
+            Self::RyzenAdj => write!(f, "RyzenAdj"),
+        }
+    }
+}
+
+/// Sustained and boost power limits (TDP), in watts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TdpSettings {
+    /// Sustained package power limit (PL1 / STAPM), watts
+    pub spl: u32,
+    /// Slow/boost package power limit (PL2), watts
+    pub sppt: u32,
+    /// Fast/boost package power limit, watts
+    pub fppt: u32,
+}
+
+impl Default for TdpSettings {
+    fn default() -> Self {
+        Self { spl: 15, sppt: 20, fppt: 25 }
+    }
+}
+
 /// Supported ASUS laptop features
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct HardwareCapabilities {
     /// Whether performance mode switching is supported
     pub performance_modes: bool,
+    /// Performance modes the current firmware's `platform_profile_choices`
+    /// actually advertises, so the GUI can gray out the rest. Falls back to
+    /// Silent/Balanced/Turbo when `platform_profile_choices` couldn't be read
+    /// (older firmware); empty when there is no `platform_profile` support.
+    pub available_performance_modes: Vec<PerformanceMode>,
     /// Whether GPU mode switching is supported
     pub gpu_switching: bool,
     /// Whether fan control is supported
@@ -277,6 +543,8 @@ pub struct HardwareCapabilities {
     pub panel_overdrive: bool,
     /// Whether Anime Matrix display is supported
     pub anime_matrix: bool,
+    /// Whether TDP (power-limit) control is supported, and by which backend
+    pub tdp_backend: Option<TdpBackend>,
     /// Model name if detected
     pub model_name: Option<String>,
 }
@@ -1,8 +1,10 @@
 //! Daemon configuration management
 
 use anyhow::Result;
+use asus_armoury_common::RgbBackend;
 use directories::ProjectDirs;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -21,6 +23,28 @@ pub struct DaemonConfig {
     pub use_asusctl: bool,
     /// Whether to integrate with supergfxctl for GPU switching
     pub use_supergfxctl: bool,
+    /// Last variant id applied per profile name, recorded whenever a variant
+    /// is applied; applying a profile still starts from its default variant
+    #[serde(default)]
+    pub last_variant: HashMap<String, u64>,
+    /// Which backend drives the RGB keyboard; `NativeHid` is only honored
+    /// when an Aura HID controller is actually detected, otherwise the
+    /// daemon falls back to the sysfs/asusctl path regardless
+    #[serde(default)]
+    pub rgb_backend: RgbBackend,
+    /// Directory the sensor monitor writes its rotating CSV log(s) into
+    #[serde(default = "DaemonConfig::default_monitor_log_dir")]
+    pub monitor_log_dir: PathBuf,
+    /// How often the sensor monitor samples telemetry, in milliseconds
+    #[serde(default = "DaemonConfig::default_monitor_sample_interval_ms")]
+    pub monitor_sample_interval_ms: u64,
+    /// Rotate the active CSV once it reaches this size in bytes
+    #[serde(default = "DaemonConfig::default_monitor_max_file_bytes")]
+    pub monitor_max_file_bytes: u64,
+    /// Rotate the active CSV once it's older than this many seconds,
+    /// regardless of size
+    #[serde(default = "DaemonConfig::default_monitor_max_file_age_secs")]
+    pub monitor_max_file_age_secs: u64,
 }
 
 impl Default for DaemonConfig {
@@ -32,6 +56,12 @@ impl Default for DaemonConfig {
             poll_interval_ms: 1000,
             use_asusctl: true,
             use_supergfxctl: true,
+            last_variant: HashMap::new(),
+            rgb_backend: RgbBackend::default(),
+            monitor_log_dir: Self::default_monitor_log_dir(),
+            monitor_sample_interval_ms: Self::default_monitor_sample_interval_ms(),
+            monitor_max_file_bytes: Self::default_monitor_max_file_bytes(),
+            monitor_max_file_age_secs: Self::default_monitor_max_file_age_secs(),
         }
     }
 }
@@ -83,4 +113,25 @@ impl DaemonConfig {
             PathBuf::from("/var/lib/asus-armoury/profiles")
         }
     }
+
+    /// Get the default sensor monitor log directory
+    fn default_monitor_log_dir() -> PathBuf {
+        if let Some(proj_dirs) = ProjectDirs::from("org", "asuslinux", "armoury") {
+            proj_dirs.data_dir().join("monitor")
+        } else {
+            PathBuf::from("/var/lib/asus-armoury/monitor")
+        }
+    }
+
+    fn default_monitor_sample_interval_ms() -> u64 {
+        2000
+    }
+
+    fn default_monitor_max_file_bytes() -> u64 {
+        10 * 1024 * 1024
+    }
+
+    fn default_monitor_max_file_age_secs() -> u64 {
+        7 * 24 * 60 * 60
+    }
 }
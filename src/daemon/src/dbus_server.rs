@@ -2,15 +2,22 @@
 
 use asus_armoury_common::{
     dbus_interface::{DBUS_NAME, DBUS_PATH},
-    FanCurve, FanCurvePoint, GpuMode, PerformanceMode, RgbEffect, RgbSettings, SystemStatus,
+    BatterySettings, FanCurve, FanCurvePoint, GpuMode, PerformanceMode, ProfileVariant, RgbEffect,
+    RgbSettings, SystemStatus, TdpSettings,
 };
-use log::{error, info};
+use log::{error, info, warn};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use zbus::{interface, Connection, ConnectionBuilder};
+use zbus::{interface, Connection, ConnectionBuilder, SignalContext};
 
 use crate::AppState;
 
+/// Minimum change in a telemetry value before `status_changed` fires, so the
+/// monitor task doesn't spam subscribers over noise in the raw sensor reads
+const TEMP_DELTA_THRESHOLD: f32 = 1.0;
+const FAN_RPM_DELTA_THRESHOLD: u32 = 50;
+const POWER_DELTA_THRESHOLD: f32 = 1.0;
+
 /// Main D-Bus interface for ASUS Armoury
 pub struct ArmouryInterface {
     state: Arc<RwLock<AppState>>,
@@ -35,23 +42,41 @@ impl ArmouryInterface {
         serde_json::to_string(&state.hardware.capabilities).unwrap_or_default()
     }
 
-    /// Get system status as JSON
-    async fn get_system_status(&self) -> String {
+    /// Get the detected model's setting limits (battery/fan/TDP ranges) as JSON
+    async fn get_limits(&self) -> String {
+        let state = self.state.read().await;
+        serde_json::to_string(&state.hardware.limits).unwrap_or_default()
+    }
+
+    /// System status as JSON, kept live via the `status_changed` signal so
+    /// clients don't need to poll this property
+    #[zbus(property)]
+    async fn system_status(&self) -> String {
         let state = self.state.read().await;
         let status = state.hardware.get_system_status();
         serde_json::to_string(&status).unwrap_or_default()
     }
 
+    /// Signal emitted by the monitor task whenever telemetry crosses a delta
+    /// threshold, carrying the serialized `SystemStatus`
+    #[zbus(signal)]
+    async fn status_changed(ctxt: &SignalContext<'_>, status: String) -> zbus::Result<()>;
+
     // ==================== Performance Mode ====================
 
-    /// Get current performance mode
-    async fn get_performance_mode(&self) -> String {
+    /// Current performance mode
+    #[zbus(property)]
+    async fn performance_mode(&self) -> String {
         let state = self.state.read().await;
         state.hardware.get_performance_mode().to_string()
     }
 
     /// Set performance mode
-    async fn set_performance_mode(&self, mode: &str) -> bool {
+    async fn set_performance_mode(
+        &self,
+        mode: &str,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+    ) -> bool {
         let mut state = self.state.write().await;
         let mode = match mode.to_lowercase().as_str() {
             "silent" => PerformanceMode::Silent,
@@ -60,9 +85,15 @@ impl ArmouryInterface {
             "manual" => PerformanceMode::Manual,
             _ => return false,
         };
-        
+
         match state.hardware.set_performance_mode(mode) {
-            Ok(()) => true,
+            Ok(()) => {
+                drop(state);
+                if let Err(e) = self.performance_mode_changed(&ctxt).await {
+                    warn!("Failed to emit PerformanceMode change: {}", e);
+                }
+                true
+            }
             Err(e) => {
                 error!("Failed to set performance mode: {}", e);
                 false
@@ -72,14 +103,19 @@ impl ArmouryInterface {
 
     // ==================== GPU Mode ====================
 
-    /// Get current GPU mode
-    async fn get_gpu_mode(&self) -> String {
+    /// Current GPU mode
+    #[zbus(property)]
+    async fn gpu_mode(&self) -> String {
         let state = self.state.read().await;
         state.hardware.get_gpu_mode().to_string()
     }
 
     /// Set GPU mode
-    async fn set_gpu_mode(&self, mode: &str) -> bool {
+    async fn set_gpu_mode(
+        &self,
+        mode: &str,
+        #[zbus(signal_context)] ctxt: SignalContext<'_>,
+    ) -> bool {
         let mut state = self.state.write().await;
         let mode = match mode.to_lowercase().as_str() {
             "integrated" => GpuMode::Integrated,
@@ -88,9 +124,15 @@ impl ArmouryInterface {
             "compute" => GpuMode::Compute,
             _ => return false,
         };
-        
+
         match state.hardware.set_gpu_mode(mode) {
-            Ok(()) => true,
+            Ok(()) => {
+                drop(state);
+                if let Err(e) = self.gpu_mode_changed(&ctxt).await {
+                    warn!("Failed to emit GpuMode change: {}", e);
+                }
+                true
+            }
             Err(e) => {
                 error!("Failed to set GPU mode: {}", e);
                 false
@@ -100,8 +142,9 @@ impl ArmouryInterface {
 
     // ==================== Fan Control ====================
 
-    /// Get fan speeds as JSON { "cpu": rpm, "gpu": rpm }
-    async fn get_fan_speeds(&self) -> String {
+    /// Fan speeds as JSON { "cpu": rpm, "gpu": rpm }
+    #[zbus(property)]
+    async fn fan_speeds(&self) -> String {
         let state = self.state.read().await;
         let (cpu, gpu) = state.hardware.get_fan_speeds();
         serde_json::json!({ "cpu": cpu, "gpu": gpu }).to_string()
@@ -142,8 +185,9 @@ impl ArmouryInterface {
 
     // ==================== Temperature ====================
 
-    /// Get temperatures as JSON { "cpu": temp, "gpu": temp }
-    async fn get_temperatures(&self) -> String {
+    /// Temperatures as JSON { "cpu": temp, "gpu": temp }
+    #[zbus(property)]
+    async fn temperatures(&self) -> String {
         let state = self.state.read().await;
         let (cpu, gpu) = state.hardware.get_temperatures();
         serde_json::json!({ "cpu": cpu, "gpu": gpu }).to_string()
@@ -199,6 +243,64 @@ impl ArmouryInterface {
         }
     }
 
+    /// Get fine-grained battery charge-control settings as JSON
+    async fn get_battery_settings(&self) -> String {
+        let state = self.state.read().await;
+        serde_json::to_string(&state.hardware.get_battery_settings()).unwrap_or_default()
+    }
+
+    /// Set fine-grained battery charge-control settings from JSON
+    async fn set_battery_settings(&self, settings_json: &str) -> bool {
+        let Ok(settings) = serde_json::from_str::<BatterySettings>(settings_json) else {
+            error!("Invalid battery settings JSON");
+            return false;
+        };
+
+        let mut state = self.state.write().await;
+        match state.hardware.set_battery_settings(&settings) {
+            Ok(()) => true,
+            Err(e) => {
+                error!("Failed to set battery settings: {}", e);
+                false
+            }
+        }
+    }
+
+    // ==================== Power Limits (TDP) ====================
+
+    /// Get current TDP settings as JSON, or an empty string if unsupported
+    async fn get_tdp(&self) -> String {
+        let state = self.state.read().await;
+        match state.hardware.get_tdp() {
+            Ok(settings) => serde_json::to_string(&settings).unwrap_or_default(),
+            Err(e) => {
+                error!("Failed to read TDP settings: {}", e);
+                String::new()
+            }
+        }
+    }
+
+    /// Set TDP settings from JSON
+    async fn set_tdp(&self, settings_json: &str) -> bool {
+        let mut state = self.state.write().await;
+
+        let settings: TdpSettings = match serde_json::from_str(settings_json) {
+            Ok(s) => s,
+            Err(e) => {
+                error!("Invalid TDP settings JSON: {}", e);
+                return false;
+            }
+        };
+
+        match state.hardware.set_tdp(&settings) {
+            Ok(()) => true,
+            Err(e) => {
+                error!("Failed to set TDP settings: {}", e);
+                false
+            }
+        }
+    }
+
     // ==================== Profiles ====================
 
     /// List available profiles
@@ -226,47 +328,87 @@ impl ArmouryInterface {
         }
     }
 
-    /// Apply profile by name
+    /// Get a summary of the most recent profile load pass (counts of
+    /// profiles loaded as-is, migrated from an older schema version, and
+    /// quarantined for failing to parse) as JSON, so the GUI can surface any
+    /// problems to the user after startup
+    async fn profile_load_summary(&self) -> String {
+        let state = self.state.read().await;
+        serde_json::to_string(state.profiles.load_summary()).unwrap_or_default()
+    }
+
+    /// Apply profile by name, resolving to its default variant if it has any.
+    /// Manually applying a profile pins it, so the auto-switch policy engine
+    /// leaves it alone until the user unpins it.
     async fn apply_profile(&self, name: &str) -> bool {
         let mut state = self.state.write().await;
-        
-        let profile = match state.profiles.get_profile(name) {
-            Some(p) => p.clone(),
+        let success = apply_profile_by_name(&mut state, name);
+        if success {
+            state.profiles.pin();
+        }
+        success
+    }
+
+    // ==================== Profile Variants ====================
+
+    /// List a profile's variants (id/name only) as JSON
+    async fn list_variants(&self, profile_name: &str) -> String {
+        let state = self.state.read().await;
+        serde_json::to_string(&state.profiles.list_variants(profile_name)).unwrap_or_default()
+    }
+
+    /// Apply one of a profile's variants by id
+    async fn apply_variant(&self, profile_name: &str, variant_id: u64) -> bool {
+        let mut state = self.state.write().await;
+
+        let variant = match state.profiles.get_variant(profile_name, variant_id) {
+            Some(v) => v,
             None => {
-                error!("Profile not found: {}", name);
+                error!("Variant {} not found on profile {}", variant_id, profile_name);
                 return false;
             }
         };
 
-        // Apply all settings from the profile
-        let mut success = true;
+        let success = apply_settings(
+            &mut *state,
+            variant.performance_mode,
+            &variant.rgb_settings,
+            &variant.battery_settings,
+            variant.fan_curve.as_ref(),
+            variant.tdp_settings.as_ref(),
+        );
 
-        if let Err(e) = state.hardware.set_performance_mode(profile.performance_mode) {
-            error!("Failed to set performance mode: {}", e);
-            success = false;
+        if success {
+            state.profiles.set_current_profile(profile_name);
+            state.profiles.pin();
+            state.config.last_variant.insert(profile_name.to_string(), variant_id);
+            if let Err(e) = state.config.save() {
+                warn!("Failed to persist last-applied variant: {}", e);
+            }
         }
 
-        if let Err(e) = state.hardware.set_rgb_settings(&profile.rgb_settings) {
-            error!("Failed to set RGB settings: {}", e);
-            // Don't fail completely if RGB fails
-        }
+        success
+    }
 
-        if let Err(e) = state.hardware.set_battery_limit(profile.battery_settings.charge_limit) {
-            error!("Failed to set battery limit: {}", e);
-            // Don't fail completely if battery limit fails
-        }
+    /// Add or replace a variant on a profile from JSON
+    async fn save_variant(&self, profile_name: &str, variant_json: &str) -> bool {
+        let mut state = self.state.write().await;
 
-        if let Some(ref curve) = profile.fan_curve {
-            if let Err(e) = state.hardware.set_fan_curve(curve) {
-                error!("Failed to set fan curve: {}", e);
+        let variant: ProfileVariant = match serde_json::from_str(variant_json) {
+            Ok(v) => v,
+            Err(e) => {
+                error!("Invalid variant JSON: {}", e);
+                return false;
             }
-        }
+        };
 
-        if success {
-            state.profiles.set_current_profile(name);
+        match state.profiles.save_variant(profile_name, variant) {
+            Ok(()) => true,
+            Err(e) => {
+                error!("Failed to save variant: {}", e);
+                false
+            }
         }
-
-        success
     }
 
     /// Save profile from JSON
@@ -301,12 +443,228 @@ impl ArmouryInterface {
             }
         }
     }
+
+    // ==================== Auto-Switch Rules ====================
+
+    /// Whether the auto-switch policy engine is enabled
+    async fn auto_switch_enabled(&self) -> bool {
+        self.state.read().await.profiles.auto_switch_enabled()
+    }
+
+    /// Enable or disable the auto-switch policy engine
+    async fn set_auto_switch_enabled(&self, enabled: bool) -> bool {
+        let mut state = self.state.write().await;
+        match state.profiles.set_auto_switch_enabled(enabled) {
+            Ok(()) => true,
+            Err(e) => {
+                error!("Failed to persist auto-switch enabled flag: {}", e);
+                false
+            }
+        }
+    }
+
+    /// List the auto-switch rules as JSON, in evaluation order
+    async fn list_auto_switch_rules(&self) -> String {
+        let state = self.state.read().await;
+        serde_json::to_string(state.profiles.list_auto_switch_rules()).unwrap_or_default()
+    }
+
+    /// Add a new auto-switch rule from a JSON-encoded `AutoSwitchCondition`
+    /// and a target profile name; returns the new rule's id, or 0 on failure
+    async fn add_auto_switch_rule(&self, condition_json: &str, profile_name: &str) -> u64 {
+        let mut state = self.state.write().await;
+
+        let condition = match serde_json::from_str(condition_json) {
+            Ok(c) => c,
+            Err(e) => {
+                error!("Invalid auto-switch condition JSON: {}", e);
+                return 0;
+            }
+        };
+
+        match state.profiles.add_auto_switch_rule(condition, profile_name.to_string()) {
+            Ok(id) => id,
+            Err(e) => {
+                error!("Failed to add auto-switch rule: {}", e);
+                0
+            }
+        }
+    }
+
+    /// Remove an auto-switch rule by id
+    async fn remove_auto_switch_rule(&self, id: u64) -> bool {
+        let mut state = self.state.write().await;
+        match state.profiles.remove_auto_switch_rule(id) {
+            Ok(()) => true,
+            Err(e) => {
+                error!("Failed to remove auto-switch rule: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Unpin the active profile, letting the auto-switch policy engine
+    /// resume switching on its next evaluation
+    async fn unpin_profile(&self) {
+        self.state.write().await.profiles.unpin();
+    }
+
+    /// Whether the active profile is currently pinned against auto-switching
+    async fn is_profile_pinned(&self) -> bool {
+        self.state.read().await.profiles.is_pinned()
+    }
+
+    // ==================== Sensor Monitoring ====================
+
+    /// Start the sensor logging subsystem; a no-op (returns `true`) if it's
+    /// already running
+    async fn start_monitoring(&self) -> bool {
+        let mut state = self.state.write().await;
+        if state.monitor.is_some() {
+            return true;
+        }
+
+        let policy = crate::monitor::RetentionPolicy {
+            max_file_bytes: state.config.monitor_max_file_bytes,
+            max_file_age_secs: state.config.monitor_max_file_age_secs,
+        };
+
+        match crate::monitor::start(
+            self.state.clone(),
+            state.config.monitor_log_dir.clone(),
+            state.config.monitor_sample_interval_ms,
+            policy,
+        ) {
+            Ok(handle) => {
+                state.monitor = Some(handle);
+                true
+            }
+            Err(e) => {
+                error!("Failed to start sensor monitoring: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Stop the sensor logging subsystem; a no-op (returns `true`) if it
+    /// wasn't running
+    async fn stop_monitoring(&self) -> bool {
+        let mut state = self.state.write().await;
+        if let Some(handle) = state.monitor.take() {
+            handle.stop();
+        }
+        true
+    }
+
+    /// Whether the sensor logging subsystem is currently running
+    async fn is_monitoring(&self) -> bool {
+        self.state.read().await.monitor.is_some()
+    }
+
+    /// Recent sampled telemetry as a JSON array, newest last, for the GUI to
+    /// graph without reading the CSV log itself. Empty when not running.
+    async fn monitoring_snapshot(&self) -> String {
+        let state = self.state.read().await;
+        let Some(handle) = &state.monitor else {
+            return "[]".to_string();
+        };
+        serde_json::to_string(&handle.snapshot().await).unwrap_or_else(|_| "[]".to_string())
+    }
+}
+
+/// Apply a profile by name, resolving to its default variant if it has any.
+/// Shared by the `apply_profile` D-Bus method and the auto-switch policy
+/// engine, so an automatic switch pushes settings identically to a manual
+/// one; callers decide separately whether the switch should pin the profile.
+pub(crate) fn apply_profile_by_name(state: &mut AppState, name: &str) -> bool {
+    let success = if let Some(variant) = state.profiles.default_variant(name) {
+        let variant_id = variant.info.id;
+        let applied = apply_settings(
+            state,
+            variant.performance_mode,
+            &variant.rgb_settings,
+            &variant.battery_settings,
+            variant.fan_curve.as_ref(),
+            variant.tdp_settings.as_ref(),
+        );
+        state.config.last_variant.insert(name.to_string(), variant_id);
+        if let Err(e) = state.config.save() {
+            warn!("Failed to persist last-applied variant: {}", e);
+        }
+        applied
+    } else {
+        let profile = match state.profiles.get_profile(name) {
+            Some(p) => p.clone(),
+            None => {
+                error!("Profile not found: {}", name);
+                return false;
+            }
+        };
+        apply_settings(
+            state,
+            profile.performance_mode,
+            &profile.rgb_settings,
+            &profile.battery_settings,
+            profile.fan_curve.as_ref(),
+            profile.tdp_settings.as_ref(),
+        )
+    };
+
+    if success {
+        state.profiles.set_current_profile(name);
+    }
+
+    success
+}
+
+/// Push one concrete set of settings (a profile's own fields, or one of its
+/// variants) to the hardware. Shared by `apply_profile_by_name` and
+/// `apply_variant` so the two stay behaviorally identical. RGB/battery/fan-
+/// curve/TDP failures are logged but don't fail the whole apply; only the
+/// performance mode is load-bearing for the return value.
+fn apply_settings(
+    state: &mut AppState,
+    performance_mode: PerformanceMode,
+    rgb_settings: &RgbSettings,
+    battery_settings: &BatterySettings,
+    fan_curve: Option<&FanCurve>,
+    tdp_settings: Option<&TdpSettings>,
+) -> bool {
+    let mut success = true;
+
+    if let Err(e) = state.hardware.set_performance_mode(performance_mode) {
+        error!("Failed to set performance mode: {}", e);
+        success = false;
+    }
+
+    if let Err(e) = state.hardware.set_rgb_settings(rgb_settings) {
+        error!("Failed to set RGB settings: {}", e);
+    }
+
+    if let Err(e) = state.hardware.set_battery_settings(battery_settings) {
+        error!("Failed to set battery settings: {}", e);
+    }
+
+    if let Some(curve) = fan_curve {
+        if let Err(e) = state.hardware.set_fan_curve(curve) {
+            error!("Failed to set fan curve: {}", e);
+        }
+    }
+
+    if let Some(tdp) = tdp_settings {
+        if let Err(e) = state.hardware.set_tdp(tdp) {
+            error!("Failed to set TDP settings: {}", e);
+        }
+    }
+
+    success
 }
 
 /// Run the D-Bus server
 pub async fn run_server(state: Arc<RwLock<AppState>>) -> anyhow::Result<()> {
-    let interface = ArmouryInterface::new(state);
-    
+    let poll_interval_ms = state.read().await.config.poll_interval_ms;
+    let interface = ArmouryInterface::new(state.clone());
+
     let connection = ConnectionBuilder::system()?
         .name(DBUS_NAME)?
         .serve_at(DBUS_PATH, interface)?
@@ -315,8 +673,69 @@ pub async fn run_server(state: Arc<RwLock<AppState>>) -> anyhow::Result<()> {
 
     info!("D-Bus server running at {} ({})", DBUS_NAME, DBUS_PATH);
 
+    spawn_status_monitor(connection.clone(), state, poll_interval_ms).await;
+
     // Keep the server running
     loop {
         tokio::time::sleep(tokio::time::Duration::from_secs(3600)).await;
     }
 }
+
+/// Poll `HardwareController::get_system_status()` on an interval and emit
+/// `status_changed` only once the reading has moved by more than a noise
+/// threshold, so subscribers get push updates instead of having to poll
+async fn spawn_status_monitor(
+    connection: Connection,
+    state: Arc<RwLock<AppState>>,
+    poll_interval_ms: u64,
+) {
+    let interval = std::time::Duration::from_millis(poll_interval_ms.max(100));
+
+    let iface_ref = match connection
+        .object_server()
+        .interface::<_, ArmouryInterface>(DBUS_PATH)
+        .await
+    {
+        Ok(iface_ref) => iface_ref,
+        Err(e) => {
+            warn!("Failed to get ArmouryInterface reference for status monitor: {}", e);
+            return;
+        }
+    };
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        let mut last_status: Option<SystemStatus> = None;
+
+        loop {
+            ticker.tick().await;
+            let status = state.read().await.hardware.get_system_status();
+
+            let should_emit = match &last_status {
+                Some(prev) => status_changed_enough(prev, &status),
+                None => true,
+            };
+
+            if should_emit {
+                let ctxt = iface_ref.signal_context();
+                if let Ok(json) = serde_json::to_string(&status) {
+                    if let Err(e) = ArmouryInterface::status_changed(ctxt, json).await {
+                        warn!("Failed to emit status_changed: {}", e);
+                    }
+                }
+                last_status = Some(status);
+            }
+        }
+    });
+}
+
+/// Whether `new` has moved far enough past `prev` to be worth notifying about
+fn status_changed_enough(prev: &SystemStatus, new: &SystemStatus) -> bool {
+    (new.cpu_temp - prev.cpu_temp).abs() >= TEMP_DELTA_THRESHOLD
+        || (new.gpu_temp - prev.gpu_temp).abs() >= TEMP_DELTA_THRESHOLD
+        || new.cpu_fan_rpm.abs_diff(prev.cpu_fan_rpm) >= FAN_RPM_DELTA_THRESHOLD
+        || new.gpu_fan_rpm.abs_diff(prev.gpu_fan_rpm) >= FAN_RPM_DELTA_THRESHOLD
+        || (new.power_draw - prev.power_draw).abs() >= POWER_DELTA_THRESHOLD
+        || new.battery_percent != prev.battery_percent
+        || new.ac_connected != prev.ac_connected
+}
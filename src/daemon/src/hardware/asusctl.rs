@@ -74,14 +74,19 @@ pub fn set_led_mode(settings: &RgbSettings) -> ArmouryResult<()> {
         RgbEffect::Wave => "comet",
         RgbEffect::Spectrum => "rainbow",
         RgbEffect::Reactive => "pulse",
+        // Driven by a live-computed RgbColor rather than a hardware animation mode
+        RgbEffect::Temperature => "static",
         RgbEffect::Off => "off",
     };
 
     let mut args = vec!["led-mode", "-s", mode];
-    
+
     // Add color if applicable
     let color_hex = settings.color.to_hex();
-    if matches!(settings.effect, RgbEffect::Static | RgbEffect::Breathing | RgbEffect::Reactive) {
+    if matches!(
+        settings.effect,
+        RgbEffect::Static | RgbEffect::Breathing | RgbEffect::Reactive | RgbEffect::Temperature
+    ) {
         args.extend(["-c", &color_hex]);
     }
 
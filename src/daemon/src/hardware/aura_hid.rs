@@ -0,0 +1,182 @@
+//! ASUS Aura USB HID RGB backend
+//!
+//! Fallback keyboard RGB path for models where `SysfsInterface::has_rgb_keyboard`
+//! finds neither the `asus::kbd_backlight` LED class nor an `aura_keyboard` WMI
+//! attribute - typically ROG laptops whose keyboard controller is only reachable
+//! over its USB HID interface, the same one `asusctl`/`rog-aura` drive directly
+//! rather than through a kernel LED class.
+
+use asus_armoury_common::{ArmouryError, ArmouryResult, RgbColor, RgbEffect, RgbSettings};
+use hidapi::{HidApi, HidDevice};
+
+/// ASUS vendor id on the USB bus
+const ASUS_VENDOR_ID: u16 = 0x0b05;
+
+/// Known Aura keyboard controller product ids (ROG Zephyrus/Strix generations)
+const AURA_PRODUCT_IDS: &[u16] = &[0x1854, 0x1869, 0x1866, 0x19b6];
+
+/// Report id the keyboard controller expects on every report
+const REPORT_ID: u8 = 0x5d;
+/// Command code for "set lighting"
+const CMD_SET_LED: u8 = 0xb3;
+/// Command code for "apply/commit" - latches the last `CMD_SET_LED` report
+const CMD_APPLY: u8 = 0xb4;
+/// Command code for "read configuration table"
+const CMD_GET_CONFIG: u8 = 0x05;
+/// Fixed HID report length the controller expects, report id included
+const REPORT_LEN: usize = 64;
+
+/// Whole-keyboard zone; per-key addressing pages through zone ids 1..=N
+const ZONE_WHOLE_KEYBOARD: u8 = 0x00;
+/// Offset of the LED count byte in the `CMD_GET_CONFIG` response
+const CONFIG_LED_COUNT_OFFSET: usize = 2;
+/// Offset of the RGB-header (external/addressable strip) count byte
+const CONFIG_RGB_HEADER_COUNT_OFFSET: usize = 3;
+/// Conservative zone cap used if the config table can't be read, so a
+/// per-zone write still degrades to something rather than erroring out
+const FALLBACK_ZONE_COUNT: u8 = 1;
+
+/// Controller layout discovered from the `CMD_GET_CONFIG` report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuraConfig {
+    /// Number of independently-addressable keyboard zones
+    pub led_count: u8,
+    /// Number of auxiliary addressable RGB headers (e.g. a light bar)
+    pub rgb_header_count: u8,
+}
+
+/// Check whether an Aura HID keyboard controller is present on the USB bus
+pub fn is_available() -> bool {
+    let Ok(api) = HidApi::new() else {
+        return false;
+    };
+    api.device_list()
+        .any(|d| d.vendor_id() == ASUS_VENDOR_ID && AURA_PRODUCT_IDS.contains(&d.product_id()))
+}
+
+/// Open the Aura HID device, or an `ArmouryError` describing why not
+fn open_device(api: &HidApi) -> ArmouryResult<HidDevice> {
+    let device_info = api
+        .device_list()
+        .find(|d| d.vendor_id() == ASUS_VENDOR_ID && AURA_PRODUCT_IDS.contains(&d.product_id()))
+        .ok_or_else(|| ArmouryError::HardwareError("No Aura HID keyboard controller found".to_string()))?;
+
+    device_info
+        .open_device(api)
+        .map_err(|e| ArmouryError::HardwareError(format!("Failed to open Aura HID device: {}", e)))
+}
+
+/// Query the controller's configuration table to discover its zone layout
+pub fn read_config() -> ArmouryResult<AuraConfig> {
+    let api = HidApi::new()
+        .map_err(|e| ArmouryError::HardwareError(format!("Failed to open HID API: {}", e)))?;
+    let device = open_device(&api)?;
+    read_config_from(&device)
+}
+
+fn read_config_from(device: &HidDevice) -> ArmouryResult<AuraConfig> {
+    let mut request = [0u8; REPORT_LEN];
+    request[0] = REPORT_ID;
+    request[1] = CMD_GET_CONFIG;
+    device
+        .send_feature_report(&request)
+        .map_err(|e| ArmouryError::HardwareError(format!("Failed to request Aura config: {}", e)))?;
+
+    let mut response = [0u8; REPORT_LEN];
+    response[0] = REPORT_ID;
+    device
+        .get_feature_report(&mut response)
+        .map_err(|e| ArmouryError::HardwareError(format!("Failed to read Aura config: {}", e)))?;
+
+    Ok(AuraConfig {
+        led_count: response[CONFIG_LED_COUNT_OFFSET],
+        rgb_header_count: response[CONFIG_RGB_HEADER_COUNT_OFFSET],
+    })
+}
+
+/// Push `settings` to the keyboard over USB HID, falling back from sysfs.
+/// When `settings.zone_colors` is set, each entry is written to its own
+/// zone (clamped to the controller's discovered `led_count`); otherwise
+/// `settings.color` is applied to the whole keyboard as a single zone
+pub fn set_rgb(settings: &RgbSettings) -> ArmouryResult<()> {
+    let api = HidApi::new()
+        .map_err(|e| ArmouryError::HardwareError(format!("Failed to open HID API: {}", e)))?;
+    let device = open_device(&api)?;
+
+    match settings.zone_colors.as_deref() {
+        Some(zone_colors) if !zone_colors.is_empty() => {
+            let zone_count = read_config_from(&device)
+                .map(|cfg| cfg.led_count.max(1))
+                .unwrap_or(FALLBACK_ZONE_COUNT);
+            for (zone, color) in zone_colors.iter().enumerate().take(zone_count as usize) {
+                let report = build_set_led_report(settings, *color, zone as u8 + 1);
+                device.send_feature_report(&report).map_err(|e| {
+                    ArmouryError::HardwareError(format!("Failed to write Aura LED report: {}", e))
+                })?;
+            }
+        }
+        _ => {
+            let report = build_set_led_report(settings, settings.color, ZONE_WHOLE_KEYBOARD);
+            device.send_feature_report(&report).map_err(|e| {
+                ArmouryError::HardwareError(format!("Failed to write Aura LED report: {}", e))
+            })?;
+        }
+    }
+
+    let apply = build_apply_report();
+    device
+        .send_feature_report(&apply)
+        .map_err(|e| ArmouryError::HardwareError(format!("Failed to commit Aura LED report: {}", e)))?;
+
+    Ok(())
+}
+
+/// Build the `CMD_SET_LED` feature report: report id, command, effect id,
+/// `color`, speed, brightness, `zone`, padded to `REPORT_LEN`
+fn build_set_led_report(settings: &RgbSettings, color: RgbColor, zone: u8) -> [u8; REPORT_LEN] {
+    let mut report = [0u8; REPORT_LEN];
+    report[0] = REPORT_ID;
+    report[1] = CMD_SET_LED;
+    report[2] = aura_effect_id(settings.effect);
+    let RgbColor { r, g, b } = color;
+    report[3] = r;
+    report[4] = g;
+    report[5] = b;
+    report[6] = aura_speed(settings.speed);
+    report[7] = aura_brightness(settings.brightness);
+    report[8] = zone;
+    report
+}
+
+/// Build the `CMD_APPLY` report that latches the last `CMD_SET_LED` write
+fn build_apply_report() -> [u8; REPORT_LEN] {
+    let mut report = [0u8; REPORT_LEN];
+    report[0] = REPORT_ID;
+    report[1] = CMD_APPLY;
+    report
+}
+
+/// Map our effect enum to the Aura protocol's effect ids
+fn aura_effect_id(effect: RgbEffect) -> u8 {
+    match effect {
+        RgbEffect::Static => 0x00,
+        RgbEffect::Breathing => 0x01,
+        RgbEffect::Rainbow => 0x02,
+        RgbEffect::Wave => 0x04,
+        RgbEffect::Spectrum => 0x02,
+        RgbEffect::Reactive => 0x03,
+        // Driven by a live-computed RgbColor rather than a hardware animation mode
+        RgbEffect::Temperature => 0x00,
+        RgbEffect::Off => 0x00,
+    }
+}
+
+/// Scale our 0-100 speed into the device's 0-4 speed steps
+fn aura_speed(speed: u8) -> u8 {
+    (speed as u32 * 4 / 100).min(4) as u8
+}
+
+/// Scale our 0-100 brightness into the device's 0-3 brightness steps
+fn aura_brightness(brightness: u8) -> u8 {
+    (brightness as u32 * 3 / 100).min(3) as u8
+}
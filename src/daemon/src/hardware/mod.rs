@@ -1,14 +1,17 @@
 //! Hardware abstraction layer for ASUS laptops
 
 use asus_armoury_common::{
-    ArmouryResult, FanCurve, GpuMode, HardwareCapabilities, PerformanceMode,
-    RgbSettings, SystemStatus,
+    limits_for_model, ArmouryResult, BatterySettings, FanCurve, GpuMode, HardwareCapabilities,
+    PerformanceMode, RgbBackend, RgbSettings, SettingsLimits, SystemStatus, TdpBackend, TdpSettings,
 };
-use log::info;
+use log::{info, warn};
 use std::path::Path;
 
 mod sysfs;
 mod asusctl;
+mod ryzenadj;
+mod aura_hid;
+mod supergfxctl;
 
 pub use sysfs::SysfsInterface;
 
@@ -22,31 +25,99 @@ pub struct HardwareController {
     current_performance_mode: PerformanceMode,
     /// Current GPU mode
     current_gpu_mode: GpuMode,
+    /// Last-applied TDP settings, used to answer `get_tdp` on the ryzenadj
+    /// backend since ryzenadj has no read-back command
+    last_tdp: Option<TdpSettings>,
+    /// Last-applied RGB settings, used to answer `get_rgb_settings` since
+    /// none of the RGB backends support reading the keyboard's state back
+    current_rgb_settings: RgbSettings,
+    /// Per-model battery/fan/TDP bounds, selected by detected model name
+    pub limits: SettingsLimits,
+    /// Configured RGB backend preference; only honored when that backend is
+    /// actually available, otherwise `set_rgb_settings` falls back as usual
+    rgb_backend: RgbBackend,
+    /// Whether the user has enabled supergfxctl integration in `DaemonConfig`
+    use_supergfxctl: bool,
+    /// Fan curve being driven by software interpolation, set by `set_fan_curve`
+    /// on models with no `asus_custom_fan_curve` hwmon device; `None` when the
+    /// curve was instead programmed straight into that device
+    active_fan_curve: Option<FanCurve>,
+    /// Hysteresis bookkeeping for `poll_fan_curve`
+    fan_hysteresis: Option<FanHysteresisState>,
+}
+
+/// Duty only decreases once the temperature has dropped this many degrees
+/// below the point that last raised it, so the fan doesn't hunt at a curve
+/// breakpoint when the temperature hovers right around it
+const FAN_HYSTERESIS_DELTA_C: f32 = 3.0;
+
+/// Software fan-curve bookkeeping: the duty last written and the temperature
+/// that produced it, used to decide whether a lower target duty has earned
+/// enough of a temperature drop to actually apply
+#[derive(Debug, Clone, Copy)]
+struct FanHysteresisState {
+    last_duty: u8,
+    rising_temp: f32,
 }
 
 impl HardwareController {
     /// Create a new hardware controller and detect capabilities
-    pub fn new() -> ArmouryResult<Self> {
+    pub fn new(rgb_backend: RgbBackend, use_supergfxctl: bool) -> ArmouryResult<Self> {
         let sysfs = SysfsInterface::new();
-        let capabilities = Self::detect_capabilities(&sysfs);
-        
+        let mut capabilities = Self::detect_capabilities(&sysfs);
+        let mut limits = limits_for_model(capabilities.model_name.as_deref());
+        // Per-key RGB zoning isn't reliably probeable from sysfs, so it comes
+        // from the same per-model table as the battery/fan/TDP ranges
+        capabilities.per_key_rgb = limits.per_key_rgb;
+        // The model table's TDP ranges describe what the hardware could
+        // support; whether the control is actually reachable depends on the
+        // backend detected above, so clear the ranges when there isn't one
+        if capabilities.tdp_backend.is_none() {
+            limits.tdp_spl = None;
+            limits.tdp_sppt = None;
+            limits.tdp_fppt = None;
+        }
+
         info!("Hardware controller initialized");
-        
+
         Ok(Self {
             capabilities,
             sysfs,
             current_performance_mode: PerformanceMode::Balanced,
             current_gpu_mode: GpuMode::Hybrid,
+            last_tdp: None,
+            current_rgb_settings: RgbSettings::default(),
+            limits,
+            rgb_backend,
+            use_supergfxctl,
+            active_fan_curve: None,
+            fan_hysteresis: None,
         })
     }
 
     /// Create a dummy controller for systems without ASUS hardware
     pub fn dummy() -> Self {
+        // No backend was detected (there's no hardware at all), so the TDP
+        // ranges are unreachable regardless of what the "default" table entry says
+        let limits = SettingsLimits {
+            tdp_spl: None,
+            tdp_sppt: None,
+            tdp_fppt: None,
+            ..limits_for_model(None)
+        };
+
         Self {
             capabilities: HardwareCapabilities::default(),
             sysfs: SysfsInterface::new(),
             current_performance_mode: PerformanceMode::Balanced,
             current_gpu_mode: GpuMode::Hybrid,
+            last_tdp: None,
+            current_rgb_settings: RgbSettings::default(),
+            limits,
+            rgb_backend: RgbBackend::default(),
+            use_supergfxctl: true,
+            active_fan_curve: None,
+            fan_hysteresis: None,
         }
     }
 
@@ -65,7 +136,10 @@ impl HardwareController {
             
             // Check for platform_profile (performance modes)
             caps.performance_modes = Path::new("/sys/firmware/acpi/platform_profile").exists();
-            
+            if caps.performance_modes {
+                caps.available_performance_modes = sysfs.available_performance_modes();
+            }
+
             // Check for fan control
             caps.fan_control = sysfs.has_fan_control();
             
@@ -76,19 +150,34 @@ impl HardwareController {
             caps.rgb_keyboard = sysfs.has_rgb_keyboard();
         }
 
+        // USB HID keyboards are reachable regardless of the ASUS WMI platform
+        // driver, so they can cover models sysfs doesn't
+        if !caps.rgb_keyboard {
+            caps.rgb_keyboard = aura_hid::is_available();
+        }
+
         // Check for supergfxd (GPU switching)
         caps.gpu_switching = Self::check_supergfxd_available();
 
         // Check for anime matrix
         caps.anime_matrix = Path::new("/sys/devices/platform/asus-nb-wmi/anime_matrix").exists();
 
+        // Check for TDP control: ASUS WMI ppt_* attributes take priority, falling
+        // back to ryzenadj on AMD systems without them
+        caps.tdp_backend = if sysfs.has_tdp_wmi() {
+            Some(TdpBackend::AsusWmi)
+        } else if ryzenadj::is_available() {
+            Some(TdpBackend::RyzenAdj)
+        } else {
+            None
+        };
+
         caps
     }
 
     /// Check if supergfxd is available
     fn check_supergfxd_available() -> bool {
-        // Check if supergfxd service exists
-        Path::new("/usr/bin/supergfxctl").exists()
+        supergfxctl::is_available()
     }
 
     // ==================== Performance Mode ====================
@@ -115,49 +204,44 @@ impl HardwareController {
 
     // ==================== GPU Mode ====================
 
-    /// Get current GPU mode
+    /// Get current GPU mode, preferring a live read from supergfxctl over
+    /// the last mode we applied ourselves
     pub fn get_gpu_mode(&self) -> GpuMode {
+        if self.use_supergfxctl && supergfxctl::is_available() {
+            if let Some(mode) = supergfxctl::get_mode() {
+                return mode;
+            }
+        }
         self.current_gpu_mode
     }
 
-    /// Set GPU mode (requires supergfxctl)
+    /// Set GPU mode through supergfxctl
     pub fn set_gpu_mode(&mut self, mode: GpuMode) -> ArmouryResult<()> {
         if !self.capabilities.gpu_switching {
             return Err(asus_armoury_common::ArmouryError::FeatureNotAvailable(
                 "GPU switching not supported (supergfxctl not found)".to_string()
             ));
         }
+        if !self.use_supergfxctl {
+            return Err(asus_armoury_common::ArmouryError::FeatureNotAvailable(
+                "supergfxctl integration disabled in config".to_string()
+            ));
+        }
 
-        // Use supergfxctl to switch GPU mode
-        let mode_str = match mode {
-            GpuMode::Integrated => "Integrated",
-            GpuMode::Dedicated => "Dedicated",
-            GpuMode::Hybrid => "Hybrid",
-            GpuMode::Compute => "Compute",
-        };
-
-        let output = std::process::Command::new("supergfxctl")
-            .args(["-m", mode_str])
-            .output();
+        let action = supergfxctl::set_mode(mode)?;
+        self.current_gpu_mode = mode;
 
-        match output {
-            Ok(out) if out.status.success() => {
-                self.current_gpu_mode = mode;
-                info!("GPU mode set to: {}", mode);
-                Ok(())
+        match action {
+            supergfxctl::ModeChangeAction::RebootRequired => {
+                info!("GPU mode set to: {} (reboot required to take effect)", mode)
             }
-            Ok(out) => {
-                let stderr = String::from_utf8_lossy(&out.stderr);
-                Err(asus_armoury_common::ArmouryError::HardwareError(
-                    format!("Failed to set GPU mode: {}", stderr)
-                ))
-            }
-            Err(e) => {
-                Err(asus_armoury_common::ArmouryError::HardwareError(
-                    format!("Failed to execute supergfxctl: {}", e)
-                ))
+            supergfxctl::ModeChangeAction::LogoutRequired => {
+                info!("GPU mode set to: {} (log out and back in to take effect)", mode)
             }
+            supergfxctl::ModeChangeAction::None => info!("GPU mode set to: {}", mode),
         }
+
+        Ok(())
     }
 
     // ==================== Fan Control ====================
@@ -167,7 +251,10 @@ impl HardwareController {
         self.sysfs.read_fan_speeds()
     }
 
-    /// Set fan curve
+    /// Set fan curve. Programs the `asus_custom_fan_curve` hwmon device
+    /// directly when present; otherwise, on models that at least expose a
+    /// writable `pwm1`, falls back to software interpolation driven by
+    /// `poll_fan_curve`.
     pub fn set_fan_curve(&mut self, curve: &FanCurve) -> ArmouryResult<()> {
         if !self.capabilities.fan_control {
             return Err(asus_armoury_common::ArmouryError::FeatureNotAvailable(
@@ -175,9 +262,75 @@ impl HardwareController {
             ));
         }
 
-        self.sysfs.write_fan_curve(curve)?;
-        info!("Fan curve applied: {}", curve.name);
-        Ok(())
+        curve.validate()?;
+
+        for point in &curve.points {
+            if !self.limits.fan_temp.contains(point.temperature as u32) {
+                return Err(asus_armoury_common::ArmouryError::InvalidValue(format!(
+                    "Fan curve temperature {} out of range ({}-{})",
+                    point.temperature, self.limits.fan_temp.min, self.limits.fan_temp.max
+                )));
+            }
+            if !self.limits.fan_duty.contains(point.fan_percent as u32) {
+                return Err(asus_armoury_common::ArmouryError::InvalidValue(format!(
+                    "Fan curve duty {} out of range ({}-{})",
+                    point.fan_percent, self.limits.fan_duty.min, self.limits.fan_duty.max
+                )));
+            }
+        }
+
+        if self.sysfs.has_fan_curve_device() {
+            self.sysfs.write_fan_curve(curve)?;
+            self.active_fan_curve = None;
+            self.fan_hysteresis = None;
+            info!("Fan curve applied to hardware curve device: {}", curve.name);
+            return Ok(());
+        }
+
+        if self.sysfs.has_manual_pwm() {
+            self.active_fan_curve = Some(curve.clone());
+            self.fan_hysteresis = None;
+            info!(
+                "Fan curve \"{}\" will be driven by software interpolation (no hardware curve device)",
+                curve.name
+            );
+            return Ok(());
+        }
+
+        Err(asus_armoury_common::ArmouryError::FeatureNotAvailable(
+            "No fan curve device or writable pwm1 found".to_string()
+        ))
+    }
+
+    /// Recompute and push fan duty for the software fan-curve fallback.
+    /// No-op unless `set_fan_curve` last fell back to software control.
+    pub fn poll_fan_curve(&mut self) {
+        let Some(curve) = self.active_fan_curve.clone() else {
+            return;
+        };
+
+        let (cpu_temp, _) = self.sysfs.read_temperatures();
+        let target = curve.duty_at(cpu_temp);
+
+        let should_apply = match self.fan_hysteresis {
+            None => true,
+            Some(state) if target >= state.last_duty => true,
+            Some(state) => cpu_temp <= state.rising_temp - FAN_HYSTERESIS_DELTA_C,
+        };
+        if !should_apply {
+            return;
+        }
+
+        match self.sysfs.write_fan_duty_percent(target) {
+            Ok(()) => {
+                let rising_temp = match self.fan_hysteresis {
+                    Some(state) if target <= state.last_duty => state.rising_temp,
+                    _ => cpu_temp,
+                };
+                self.fan_hysteresis = Some(FanHysteresisState { last_duty: target, rising_temp });
+            }
+            Err(e) => warn!("Failed to push software fan duty: {}", e),
+        }
     }
 
     /// Reset fan control to automatic
@@ -203,12 +356,18 @@ impl HardwareController {
     // ==================== RGB Keyboard ====================
 
     /// Get current RGB settings
+    ///
+    /// None of our backends support reading the keyboard's state back over
+    /// the wire, so this answers from the last settings successfully pushed
+    /// by `set_rgb_settings` instead (mirrors `last_tdp` for the same reason)
     pub fn get_rgb_settings(&self) -> RgbSettings {
-        // TODO: Read from hardware
-        RgbSettings::default()
+        self.current_rgb_settings.clone()
     }
 
-    /// Set RGB settings
+    /// Set RGB settings. Uses the configured `rgb_backend` when it's actually
+    /// available (letting native HID be forced on for per-zone colors even
+    /// where sysfs also works), otherwise prefers sysfs and falls back to the
+    /// Aura USB HID backend for keyboards sysfs doesn't expose
     pub fn set_rgb_settings(&mut self, settings: &RgbSettings) -> ArmouryResult<()> {
         if !self.capabilities.rgb_keyboard {
             return Err(asus_armoury_common::ArmouryError::FeatureNotAvailable(
@@ -216,8 +375,25 @@ impl HardwareController {
             ));
         }
 
-        self.sysfs.write_rgb_settings(settings)?;
+        if !self.limits.rgb_brightness.contains(settings.brightness as u32)
+            || !self.limits.rgb_speed.contains(settings.speed as u32)
+        {
+            return Err(asus_armoury_common::ArmouryError::InvalidValue(format!(
+                "RGB settings out of range for this model: brightness {}-{}, speed {}-{}",
+                self.limits.rgb_brightness.min, self.limits.rgb_brightness.max,
+                self.limits.rgb_speed.min, self.limits.rgb_speed.max
+            )));
+        }
+
+        if self.rgb_backend == RgbBackend::NativeHid && aura_hid::is_available() {
+            aura_hid::set_rgb(settings)?;
+        } else if self.sysfs.has_rgb_keyboard() {
+            self.sysfs.write_rgb_settings(settings)?;
+        } else {
+            aura_hid::set_rgb(settings)?;
+        }
         info!("RGB settings applied: effect={}, brightness={}", settings.effect, settings.brightness);
+        self.current_rgb_settings = settings.clone();
         Ok(())
     }
 
@@ -236,12 +412,13 @@ impl HardwareController {
             ));
         }
 
-        // Validate limit
-        let valid_limits = [60, 80, 100];
-        if !valid_limits.contains(&limit) {
-            return Err(asus_armoury_common::ArmouryError::InvalidValue(
-                format!("Invalid battery limit: {}. Valid values: 60, 80, 100", limit)
-            ));
+        // Validate against this model's full allowed range rather than the
+        // historical fixed 60/80/100 set
+        if !self.limits.battery_range.contains(limit as u32) {
+            return Err(asus_armoury_common::ArmouryError::InvalidValue(format!(
+                "Invalid battery limit: {}. Valid range: {}-{} (step {})",
+                limit, self.limits.battery_range.min, self.limits.battery_range.max, self.limits.battery_range.step
+            )));
         }
 
         self.sysfs.write_battery_limit(limit)?;
@@ -249,6 +426,114 @@ impl HardwareController {
         Ok(())
     }
 
+    /// Get the current fine-grained battery charge-control settings
+    pub fn get_battery_settings(&self) -> BatterySettings {
+        BatterySettings {
+            charge_control_start_threshold: self.sysfs.read_battery_start_threshold().unwrap_or(0),
+            charge_control_end_threshold: self.sysfs.read_battery_limit().unwrap_or(100),
+            charge_rate_ma: self.sysfs.read_battery_charge_rate(),
+        }
+    }
+
+    /// Set fine-grained battery charge-control settings: start/end thresholds
+    /// and an optional charge-rate cap, each validated against this model's limits
+    pub fn set_battery_settings(&mut self, settings: &BatterySettings) -> ArmouryResult<()> {
+        if !self.capabilities.battery_limit {
+            return Err(asus_armoury_common::ArmouryError::FeatureNotAvailable(
+                "Battery charge limit not supported on this hardware".to_string()
+            ));
+        }
+
+        let range = &self.limits.battery_range;
+        if !range.contains(settings.charge_control_end_threshold as u32)
+            || !range.contains(settings.charge_control_start_threshold as u32)
+        {
+            return Err(asus_armoury_common::ArmouryError::InvalidValue(format!(
+                "Battery thresholds out of range for this model: {}-{} (step {})",
+                range.min, range.max, range.step
+            )));
+        }
+        if settings.charge_control_start_threshold > settings.charge_control_end_threshold {
+            return Err(asus_armoury_common::ArmouryError::InvalidValue(
+                "Battery start threshold cannot exceed the end threshold".to_string()
+            ));
+        }
+
+        self.sysfs.write_battery_start_threshold(settings.charge_control_start_threshold)?;
+        self.sysfs.write_battery_limit(settings.charge_control_end_threshold)?;
+        if let Some(rate) = settings.charge_rate_ma {
+            self.sysfs.write_battery_charge_rate(rate)?;
+        }
+
+        info!(
+            "Battery charge control set: start={}% end={}% rate={:?}mA",
+            settings.charge_control_start_threshold, settings.charge_control_end_threshold, settings.charge_rate_ma
+        );
+        Ok(())
+    }
+
+    // ==================== Power Limits (TDP) ====================
+
+    /// Get current TDP settings, if supported
+    pub fn get_tdp(&self) -> ArmouryResult<TdpSettings> {
+        match self.capabilities.tdp_backend {
+            Some(TdpBackend::AsusWmi) => self.sysfs.read_tdp().ok_or_else(|| {
+                asus_armoury_common::ArmouryError::HardwareError(
+                    "Failed to read TDP settings from ASUS WMI attributes".to_string()
+                )
+            }),
+            Some(TdpBackend::RyzenAdj) => Ok(self.last_tdp.unwrap_or_default()),
+            None => Err(asus_armoury_common::ArmouryError::FeatureNotAvailable(
+                "TDP control not supported on this hardware".to_string()
+            )),
+        }
+    }
+
+    /// Set TDP settings via the detected backend
+    pub fn set_tdp(&mut self, settings: &TdpSettings) -> ArmouryResult<()> {
+        let (Some(tdp_spl), Some(tdp_sppt), Some(tdp_fppt)) =
+            (self.limits.tdp_spl, self.limits.tdp_sppt, self.limits.tdp_fppt)
+        else {
+            return Err(asus_armoury_common::ArmouryError::FeatureNotAvailable(
+                "TDP control not supported on this hardware".to_string()
+            ));
+        };
+
+        if !tdp_spl.contains(settings.spl) || !tdp_sppt.contains(settings.sppt) || !tdp_fppt.contains(settings.fppt) {
+            return Err(asus_armoury_common::ArmouryError::InvalidValue(format!(
+                "TDP settings out of range for this model: spl {}-{}, sppt {}-{}, fppt {}-{}",
+                tdp_spl.min, tdp_spl.max, tdp_sppt.min, tdp_sppt.max, tdp_fppt.min, tdp_fppt.max
+            )));
+        }
+
+        match self.capabilities.tdp_backend {
+            Some(TdpBackend::AsusWmi) => {
+                self.sysfs.write_tdp(settings)?;
+            }
+            Some(TdpBackend::RyzenAdj) => {
+                ryzenadj::set_tdp(settings)?;
+            }
+            None => {
+                return Err(asus_armoury_common::ArmouryError::FeatureNotAvailable(
+                    "TDP control not supported on this hardware".to_string()
+                ));
+            }
+        }
+
+        self.last_tdp = Some(*settings);
+        info!("TDP set to: spl={}W sppt={}W fppt={}W", settings.spl, settings.sppt, settings.fppt);
+        Ok(())
+    }
+
+    // ==================== System Usage ====================
+
+    /// Sample CPU usage from `/proc/stat`, returning (aggregate %, per-core %).
+    /// Call this periodically (the daemon's poll loop does) so the underlying
+    /// snapshot stays warm and the reported percentages stay meaningful.
+    pub fn poll_cpu_usage(&self) -> (f32, Vec<f32>) {
+        self.sysfs.sample_cpu_usage()
+    }
+
     // ==================== System Status ====================
 
     /// Get comprehensive system status
@@ -0,0 +1,36 @@
+//! Integration with ryzenadj, the AMD TDP fallback backend
+//!
+//! Used when a model's firmware does not expose the ASUS WMI `ppt_*` power-limit
+//! attributes (see `SysfsInterface::has_tdp_wmi`). `ryzenadj` takes its limits in
+//! milliwatts on the command line.
+
+use asus_armoury_common::{ArmouryResult, ArmouryError, TdpSettings};
+use std::process::Command;
+
+/// Check if ryzenadj is available on the system
+pub fn is_available() -> bool {
+    Command::new("ryzenadj")
+        .arg("--info")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Apply TDP settings via ryzenadj (watts are converted to the milliwatts it expects)
+pub fn set_tdp(settings: &TdpSettings) -> ArmouryResult<()> {
+    let output = Command::new("ryzenadj")
+        .args([
+            format!("--stapm-limit={}", settings.spl * 1000),
+            format!("--slow-limit={}", settings.sppt * 1000),
+            format!("--fast-limit={}", settings.fppt * 1000),
+        ])
+        .output()
+        .map_err(|e| ArmouryError::HardwareError(format!("Failed to run ryzenadj: {}", e)))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(ArmouryError::HardwareError(format!("ryzenadj failed: {}", stderr)))
+    }
+}
@@ -0,0 +1,94 @@
+//! Integration with supergfxctl for GPU mode switching
+//!
+//! Mirrors the asusctl integration module: a thin wrapper that shells out to
+//! the `supergfxctl` CLI and translates its output to/from our own types.
+
+use asus_armoury_common::{ArmouryError, ArmouryResult, GpuMode};
+use std::process::Command;
+
+/// Whether a mode change needs the session (or the machine) restarted
+/// before it actually takes effect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModeChangeAction {
+    /// Took effect immediately
+    None,
+    /// User needs to log out and back in
+    LogoutRequired,
+    /// Full reboot needed
+    RebootRequired,
+}
+
+/// Check if supergfxctl is available on the system
+pub fn is_available() -> bool {
+    Command::new("supergfxctl")
+        .arg("-s")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Get the currently active GPU mode using supergfxctl
+pub fn get_mode() -> Option<GpuMode> {
+    let output = Command::new("supergfxctl").arg("-g").output().ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    parse_mode(&stdout)
+}
+
+/// Switch GPU mode using supergfxctl, returning whether a logout or reboot
+/// is needed before it takes effect
+pub fn set_mode(mode: GpuMode) -> ArmouryResult<ModeChangeAction> {
+    let mode_str = to_supergfx_name(mode);
+
+    let output = Command::new("supergfxctl")
+        .args(["-m", mode_str])
+        .output()
+        .map_err(|e| ArmouryError::HardwareError(format!("Failed to run supergfxctl: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ArmouryError::HardwareError(format!(
+            "supergfxctl failed to set mode: {}",
+            stderr
+        )));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout).to_lowercase();
+    Ok(if stdout.contains("reboot") {
+        ModeChangeAction::RebootRequired
+    } else if stdout.contains("logout") || stdout.contains("log out") {
+        ModeChangeAction::LogoutRequired
+    } else {
+        ModeChangeAction::None
+    })
+}
+
+/// Map our `GpuMode` to the name supergfxctl expects on its `-m` flag
+fn to_supergfx_name(mode: GpuMode) -> &'static str {
+    match mode {
+        GpuMode::Integrated => "Integrated",
+        GpuMode::Hybrid => "Hybrid",
+        GpuMode::Dedicated => "AsusMuxDgpu",
+        GpuMode::Compute => "Compute",
+    }
+}
+
+/// Parse supergfxctl's `-g` output back into our `GpuMode`
+fn parse_mode(output: &str) -> Option<GpuMode> {
+    let normalized = output.trim().to_lowercase();
+    if normalized.contains("asusmuxdgpu") || normalized.contains("dedicated") {
+        Some(GpuMode::Dedicated)
+    } else if normalized.contains("integrated") {
+        Some(GpuMode::Integrated)
+    } else if normalized.contains("compute") {
+        Some(GpuMode::Compute)
+    } else if normalized.contains("hybrid") {
+        Some(GpuMode::Hybrid)
+    } else {
+        None
+    }
+}
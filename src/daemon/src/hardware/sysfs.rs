@@ -2,10 +2,13 @@
 //!
 //! This module provides low-level access to ASUS hardware through the Linux sysfs interface.
 
-use asus_armoury_common::{ArmouryResult, ArmouryError, FanCurve, PerformanceMode, RgbSettings};
+use asus_armoury_common::{
+    interpolate_fan_points, ArmouryResult, ArmouryError, FanCurve, FanCurvePoint, PerformanceMode, RgbSettings, TdpSettings,
+};
 use log::{debug, warn};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 // ASUS-specific sysfs paths
 const PLATFORM_PROFILE: &str = "/sys/firmware/acpi/platform_profile";
@@ -13,15 +16,69 @@ const PLATFORM_PROFILE_CHOICES: &str = "/sys/firmware/acpi/platform_profile_choi
 const ASUS_WMI_PATH: &str = "/sys/devices/platform/asus-nb-wmi";
 const BATTERY_LIMIT_PATH: &str = "/sys/class/power_supply/BAT0/charge_control_end_threshold";
 const BATTERY_LIMIT_PATH_ALT: &str = "/sys/class/power_supply/BAT1/charge_control_end_threshold";
+const BATTERY_DIR: &str = "/sys/class/power_supply/BAT0";
+const BATTERY_DIR_ALT: &str = "/sys/class/power_supply/BAT1";
+/// Sibling attributes under the battery directory used by the fine-grained API
+const BATTERY_START_THRESHOLD_FILE: &str = "charge_control_start_threshold";
+const BATTERY_CHARGE_RATE_FILE: &str = "constant_charge_current_max";
+
+// ASUS WMI power-limit (PPT) attributes
+const PPT_PL1_SPL: &str = "ppt_pl1_spl";
+const PPT_PL2_SPPT: &str = "ppt_pl2_sppt";
+const PPT_FPPT: &str = "ppt_fppt";
+const PPT_APU_SPPT: &str = "ppt_apu_sppt";
+const PPT_PLATFORM_SPPT: &str = "ppt_platform_sppt";
+
+/// Inclusive watt range accepted for any single PPT attribute; writes outside
+/// it are rejected so a caller cannot push a model past a safe ceiling
+const TDP_WATT_RANGE: (u32, u32) = (5, 250);
 
 // Thermal zone paths for temperature reading
 const THERMAL_ZONE_BASE: &str = "/sys/class/thermal/thermal_zone";
 const HWMON_PATH: &str = "/sys/class/hwmon";
 
+/// `hwmon` driver `name` substrings that identify a CPU temperature source
+const CPU_TEMP_HWMON_NAMES: &[&str] = &["coretemp", "k10temp", "zenpower", "acpitz"];
+/// `hwmon` driver `name` substrings that identify a GPU temperature/usage source
+const GPU_HWMON_NAMES: &[&str] = &["nvidia", "amdgpu", "nouveau", "radeon"];
+/// `hwmon` driver `name` substrings that expose fan tachometer inputs
+const FAN_HWMON_NAMES: &[&str] = &["asus-nb-wmi", "asus_fan", "asus", "nct6", "thinkpad"];
+/// `hwmon` driver `name` for the ASUS custom fan-curve interface
+const FAN_CURVE_HWMON_NAME: &str = "asus_custom_fan_curve";
+
+/// `tempN_label` substrings that identify the CPU die/package sensor within
+/// a multi-sensor chip (e.g. `nct6` Super-IO chips expose a dozen `tempN`
+/// inputs on one node, most of them unrelated to the CPU)
+const CPU_TEMP_LABEL_HINTS: &[&str] = &["tctl", "tdie", "package", "cpu"];
+/// `tempN_label` substrings that identify the GPU die sensor
+const GPU_TEMP_LABEL_HINTS: &[&str] = &["edge", "junction", "gpu"];
+/// `fanN_label` substrings that identify the CPU fan tachometer
+const CPU_FAN_LABEL_HINTS: &[&str] = &["cpu"];
+/// `fanN_label` substrings that identify the GPU fan tachometer
+const GPU_FAN_LABEL_HINTS: &[&str] = &["gpu"];
+/// Highest `tempN`/`fanN` index probed when scanning a node's `*_label` files
+const MAX_HWMON_CHANNEL: u8 = 8;
+
+/// Number of curve points the asus_custom_fan_curve hwmon device accepts per fan
+const FAN_CURVE_HWMON_POINTS: usize = 8;
+
 /// Interface for reading/writing sysfs values
 pub struct SysfsInterface {
     /// Cached battery limit path (BAT0 or BAT1)
     battery_limit_path: Option<String>,
+    /// Battery directory (BAT0 or BAT1), used to reach the start-threshold and
+    /// charge-rate sibling attributes
+    battery_dir: Option<PathBuf>,
+    /// hwmon attribute paths resolved once at startup (the `hwmonN` index order
+    /// is not stable across boots, so every other method uses these instead of
+    /// re-scanning or guessing a path)
+    hwmon: HwmonPaths,
+    /// `platform_profile_choices` as reported by firmware at startup, e.g.
+    /// `["low-power", "balanced", "performance"]`; empty if the file doesn't
+    /// exist (older firmware exposing `platform_profile` without it)
+    platform_profile_choices: Vec<String>,
+    /// Previous `/proc/stat` snapshot, used to compute CPU usage deltas
+    cpu_stat: Mutex<Option<CpuStatSnapshot>>,
 }
 
 impl SysfsInterface {
@@ -35,7 +92,29 @@ impl SysfsInterface {
             None
         };
 
-        Self { battery_limit_path }
+        let battery_dir = if Path::new(BATTERY_DIR).exists() {
+            Some(PathBuf::from(BATTERY_DIR))
+        } else if Path::new(BATTERY_DIR_ALT).exists() {
+            Some(PathBuf::from(BATTERY_DIR_ALT))
+        } else {
+            None
+        };
+
+        Self {
+            battery_limit_path,
+            battery_dir,
+            hwmon: HwmonPaths::discover(),
+            platform_profile_choices: Self::read_platform_profile_choices(),
+            cpu_stat: Mutex::new(None),
+        }
+    }
+
+    /// Read and split `platform_profile_choices` into its space-separated
+    /// entries, e.g. `"low-power balanced performance\n"` -> 3 entries
+    fn read_platform_profile_choices() -> Vec<String> {
+        fs::read_to_string(PLATFORM_PROFILE_CHOICES)
+            .map(|content| content.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default()
     }
 
     // ==================== Model Detection ====================
@@ -66,8 +145,7 @@ impl SysfsInterface {
 
     /// Check if fan control is available
     pub fn has_fan_control(&self) -> bool {
-        let fan_curve_path = format!("{}/fan_curve", ASUS_WMI_PATH);
-        Path::new(&fan_curve_path).exists() || self.find_hwmon_fan().is_some()
+        self.hwmon.fan_curve_dir.is_some() || self.hwmon.cpu_fan.is_some()
     }
 
     /// Check if battery limit control is available
@@ -100,25 +178,76 @@ impl SysfsInterface {
     pub fn read_platform_profile(&self) -> Option<PerformanceMode> {
         let content = fs::read_to_string(PLATFORM_PROFILE).ok()?;
         let profile = content.trim();
-        
-        match profile {
-            "quiet" | "silent" => Some(PerformanceMode::Silent),
+        Self::choice_to_mode(profile).or_else(|| {
+            warn!("Unknown platform profile: {}", profile);
+            None
+        })
+    }
+
+    /// Map one `platform_profile_choices` entry to the `PerformanceMode` it
+    /// corresponds to, or `None` for a choice this daemon doesn't model
+    /// (e.g. `cool`)
+    fn choice_to_mode(choice: &str) -> Option<PerformanceMode> {
+        match choice {
+            "quiet" | "silent" | "low-power" => Some(PerformanceMode::Silent),
             "balanced" | "balanced-performance" => Some(PerformanceMode::Balanced),
             "performance" | "turbo" => Some(PerformanceMode::Turbo),
-            _ => {
-                warn!("Unknown platform profile: {}", profile);
-                None
-            }
+            _ => None,
+        }
+    }
+
+    /// Candidate `platform_profile` values for `mode`, most-preferred first,
+    /// so `write_platform_profile` can pick whichever one the firmware's
+    /// `platform_profile_choices` actually advertises
+    fn mode_to_choice_candidates(mode: PerformanceMode) -> &'static [&'static str] {
+        match mode {
+            PerformanceMode::Silent => &["quiet", "low-power", "silent"],
+            PerformanceMode::Balanced => &["balanced", "balanced-performance"],
+            PerformanceMode::Turbo => &["performance", "turbo"],
+            // Manual mode has no firmware equivalent; use balanced as a base
+            PerformanceMode::Manual => &["balanced", "balanced-performance"],
+        }
+    }
+
+    /// The performance modes the current firmware's `platform_profile_choices`
+    /// actually advertises, in a stable Silent/Balanced/Turbo order. Falls
+    /// back to all three if the choices file couldn't be read, since older
+    /// firmware exposes `platform_profile` without it but usually still
+    /// supports the standard triad.
+    pub fn available_performance_modes(&self) -> Vec<PerformanceMode> {
+        if self.platform_profile_choices.is_empty() {
+            return vec![PerformanceMode::Silent, PerformanceMode::Balanced, PerformanceMode::Turbo];
         }
+
+        [PerformanceMode::Silent, PerformanceMode::Balanced, PerformanceMode::Turbo]
+            .into_iter()
+            .filter(|mode| {
+                Self::mode_to_choice_candidates(*mode)
+                    .iter()
+                    .any(|candidate| self.platform_profile_choices.iter().any(|c| c == candidate))
+            })
+            .collect()
     }
 
-    /// Write platform profile (performance mode)
+    /// Write platform profile (performance mode), picking whichever of
+    /// `mode`'s candidate names the firmware's `platform_profile_choices`
+    /// actually supports and falling back to `balanced` when none do
     pub fn write_platform_profile(&self, mode: PerformanceMode) -> ArmouryResult<()> {
-        let profile = match mode {
-            PerformanceMode::Silent => "quiet",
-            PerformanceMode::Balanced => "balanced",
-            PerformanceMode::Turbo => "performance",
-            PerformanceMode::Manual => "balanced", // Manual uses balanced as base
+        let candidates = Self::mode_to_choice_candidates(mode);
+
+        let profile = if self.platform_profile_choices.is_empty() {
+            candidates[0]
+        } else if let Some(&supported) = candidates
+            .iter()
+            .find(|c| self.platform_profile_choices.iter().any(|choice| choice == *c))
+        {
+            supported
+        } else {
+            warn!(
+                "Firmware does not support {:?} mode (choices: {:?}), falling back to balanced",
+                mode, self.platform_profile_choices
+            );
+            "balanced"
         };
 
         fs::write(PLATFORM_PROFILE, profile).map_err(|e| {
@@ -130,6 +259,70 @@ impl SysfsInterface {
         })
     }
 
+    // ==================== Power Limits (TDP) ====================
+
+    /// Whether the ASUS WMI power-limit (PPT) attributes are present on this hardware
+    pub fn has_tdp_wmi(&self) -> bool {
+        Path::new(&format!("{}/{}", ASUS_WMI_PATH, PPT_PL1_SPL)).exists()
+    }
+
+    /// Read the current TDP settings from the ASUS WMI attributes
+    pub fn read_tdp(&self) -> Option<TdpSettings> {
+        Some(TdpSettings {
+            spl: self.read_ppt_attr(PPT_PL1_SPL)?,
+            sppt: self.read_ppt_attr(PPT_PL2_SPPT)?,
+            fppt: self.read_ppt_attr(PPT_FPPT)?,
+        })
+    }
+
+    /// Write TDP settings to the ASUS WMI attributes, rejecting any value
+    /// outside `TDP_WATT_RANGE`
+    pub fn write_tdp(&self, settings: &TdpSettings) -> ArmouryResult<()> {
+        if !self.has_tdp_wmi() {
+            return Err(ArmouryError::FeatureNotAvailable(
+                "ASUS WMI power-limit attributes not available".to_string()
+            ));
+        }
+
+        self.write_ppt_attr(PPT_PL1_SPL, settings.spl)?;
+        self.write_ppt_attr(PPT_PL2_SPPT, settings.sppt)?;
+        self.write_ppt_attr(PPT_FPPT, settings.fppt)?;
+
+        // Best-effort: mirror the slow (sppt) limit onto the APU/platform
+        // attributes where the firmware exposes them
+        let _ = self.write_ppt_attr(PPT_APU_SPPT, settings.sppt);
+        let _ = self.write_ppt_attr(PPT_PLATFORM_SPPT, settings.sppt);
+
+        Ok(())
+    }
+
+    fn read_ppt_attr(&self, attr: &str) -> Option<u32> {
+        let path = format!("{}/{}", ASUS_WMI_PATH, attr);
+        fs::read_to_string(path).ok()?.trim().parse::<u32>().ok()
+    }
+
+    fn write_ppt_attr(&self, attr: &str, watts: u32) -> ArmouryResult<()> {
+        let path = format!("{}/{}", ASUS_WMI_PATH, attr);
+        if !Path::new(&path).exists() {
+            return Err(ArmouryError::FeatureNotAvailable(format!("{} not available", attr)));
+        }
+
+        let (min, max) = TDP_WATT_RANGE;
+        if watts < min || watts > max {
+            return Err(ArmouryError::InvalidValue(format!(
+                "TDP value must be between {} and {} watts (got {})", min, max, watts
+            )));
+        }
+
+        fs::write(&path, watts.to_string()).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                ArmouryError::PermissionDenied(format!("Cannot write {} (root required)", attr))
+            } else {
+                ArmouryError::IoError(e)
+            }
+        })
+    }
+
     // ==================== Temperature Reading ====================
 
     /// Read CPU and GPU temperatures
@@ -157,9 +350,9 @@ impl SysfsInterface {
             }
         }
 
-        // Try hwmon
-        if let Some(hwmon) = self.find_hwmon_cpu() {
-            if let Ok(temp_str) = fs::read_to_string(format!("{}/temp1_input", hwmon)) {
+        // Try the hwmon path resolved at startup
+        if let Some(path) = &self.hwmon.cpu_temp {
+            if let Ok(temp_str) = fs::read_to_string(path) {
                 if let Ok(temp) = temp_str.trim().parse::<f32>() {
                     return Some(temp / 1000.0);
                 }
@@ -170,9 +363,16 @@ impl SysfsInterface {
     }
 
     fn read_gpu_temperature(&self) -> Option<f32> {
-        // Try NVIDIA GPU
-        if let Some(hwmon) = self.find_hwmon_gpu() {
-            if let Ok(temp_str) = fs::read_to_string(format!("{}/temp1_input", hwmon)) {
+        // Try the AMD gpu_metrics binary table first - covers APUs with no hwmon
+        if let Some(metrics) = self.read_gpu_metrics() {
+            if let Some(temp) = metrics.temp_gfx.or(metrics.temp_edge) {
+                return Some(temp);
+            }
+        }
+
+        // Try the hwmon path resolved at startup (NVIDIA, or AMD without gpu_metrics)
+        if let Some(path) = &self.hwmon.gpu_temp {
+            if let Ok(temp_str) = fs::read_to_string(path) {
                 if let Ok(temp) = temp_str.trim().parse::<f32>() {
                     return Some(temp / 1000.0);
                 }
@@ -209,59 +409,144 @@ impl SysfsInterface {
     }
 
     fn read_fan_rpm(&self, fan_num: u8) -> Option<u32> {
-        if let Some(hwmon) = self.find_hwmon_fan() {
-            let path = format!("{}/fan{}_input", hwmon, fan_num);
-            if let Ok(rpm_str) = fs::read_to_string(&path) {
-                if let Ok(rpm) = rpm_str.trim().parse::<u32>() {
-                    return Some(rpm);
-                }
-            }
-        }
-        None
+        let path = match fan_num {
+            1 => self.hwmon.cpu_fan.as_ref(),
+            2 => self.hwmon.gpu_fan.as_ref(),
+            _ => None,
+        }?;
+
+        fs::read_to_string(path).ok()?.trim().parse::<u32>().ok()
     }
 
-    /// Write fan curve to hardware
+    /// Write fan curve to hardware via the `asus_custom_fan_curve` hwmon device
+    ///
+    /// The kernel interface exposes `pwm1` (CPU) and `pwm2` (GPU), each taking exactly
+    /// 8 curve points through `pwmN_auto_pointM_{temp,pwm}`. We resample the crate's
+    /// `FanCurve` to 8 points and apply it to both fans.
     pub fn write_fan_curve(&self, curve: &FanCurve) -> ArmouryResult<()> {
-        let fan_curve_path = format!("{}/fan_curve", ASUS_WMI_PATH);
-        
-        if !Path::new(&fan_curve_path).exists() {
-            return Err(ArmouryError::FeatureNotAvailable(
-                "Fan curve control not available".to_string()
-            ));
-        }
+        let hwmon = self.hwmon.fan_curve_dir.as_ref().ok_or_else(|| {
+            ArmouryError::FeatureNotAvailable("Fan curve control not available".to_string())
+        })?;
 
-        // Format fan curve for ASUS WMI
-        // Format: temp1:speed1,temp2:speed2,...
-        let curve_str: String = curve.points
-            .iter()
-            .map(|p| format!("{}:{}", p.temperature, p.fan_percent))
-            .collect::<Vec<_>>()
-            .join(",");
+        let points = Self::resample_curve(&curve.points, FAN_CURVE_HWMON_POINTS);
 
-        fs::write(&fan_curve_path, &curve_str).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::PermissionDenied {
-                ArmouryError::PermissionDenied("Cannot write fan curve (root required)".to_string())
-            } else {
-                ArmouryError::IoError(e)
+        for pwm in 1..=2u8 {
+            self.write_fan_curve_enable(hwmon, pwm, 1)?;
+
+            for (i, point) in points.iter().enumerate() {
+                let index = i + 1;
+                let pwm_value = (point.fan_percent as u32 * 255 / 100).min(255);
+
+                fs::write(
+                    format!("{}/pwm{}_auto_point{}_temp", hwmon.display(), pwm, index),
+                    point.temperature.to_string(),
+                )
+                .map_err(Self::map_io_error)?;
+
+                fs::write(
+                    format!("{}/pwm{}_auto_point{}_pwm", hwmon.display(), pwm, index),
+                    pwm_value.to_string(),
+                )
+                .map_err(Self::map_io_error)?;
             }
-        })
+        }
+
+        Ok(())
     }
 
-    /// Reset fan to automatic control
+    /// Reset fan control to automatic (factory auto mode, curve retained)
     pub fn reset_fan_auto(&self) -> ArmouryResult<()> {
-        let fan_curve_path = format!("{}/fan_curve", ASUS_WMI_PATH);
-        
-        if Path::new(&fan_curve_path).exists() {
-            fs::write(&fan_curve_path, "auto").map_err(|e| {
-                if e.kind() == std::io::ErrorKind::PermissionDenied {
-                    ArmouryError::PermissionDenied("Cannot reset fan control (root required)".to_string())
+        let Some(hwmon) = self.hwmon.fan_curve_dir.as_ref() else {
+            return Ok(()); // No fan curve support, nothing to reset
+        };
+
+        for pwm in 1..=2u8 {
+            self.write_fan_curve_enable(hwmon, pwm, 2)?;
+        }
+
+        Ok(())
+    }
+
+    /// Restore the factory default fan curve, discarding any custom points
+    pub fn restore_factory_fan_curve(&self) -> ArmouryResult<()> {
+        let Some(hwmon) = self.hwmon.fan_curve_dir.as_ref() else {
+            return Ok(());
+        };
+
+        for pwm in 1..=2u8 {
+            self.write_fan_curve_enable(hwmon, pwm, 3)?;
+        }
+
+        Ok(())
+    }
+
+    fn write_fan_curve_enable(&self, hwmon: &Path, pwm: u8, value: u8) -> ArmouryResult<()> {
+        fs::write(format!("{}/pwm{}_enable", hwmon.display(), pwm), value.to_string())
+            .map_err(Self::map_io_error)
+    }
+
+    /// Whether the `asus_custom_fan_curve` hwmon device is present, so the
+    /// curve can be programmed straight into hardware
+    pub fn has_fan_curve_device(&self) -> bool {
+        self.hwmon.fan_curve_dir.is_some()
+    }
+
+    /// Whether `pwm1` is writable directly, for the software fan-curve
+    /// fallback used on models with no `asus_custom_fan_curve` hwmon device
+    pub fn has_manual_pwm(&self) -> bool {
+        self.hwmon.cpu_pwm.is_some()
+    }
+
+    /// Switch `pwm1` into manual mode and write a duty percentage to it.
+    /// Used by the daemon's software interpolation loop rather than the
+    /// `asus_custom_fan_curve` hardware curve device.
+    pub fn write_fan_duty_percent(&self, percent: u8) -> ArmouryResult<()> {
+        let pwm = self.hwmon.cpu_pwm.as_ref().ok_or_else(|| {
+            ArmouryError::FeatureNotAvailable("No writable pwm1 attribute found".to_string())
+        })?;
+
+        if let Some(enable) = &self.hwmon.cpu_pwm_enable {
+            fs::write(enable, "1").map_err(Self::map_io_error)?;
+        }
+
+        let duty_255 = (percent.min(100) as u32 * 255 / 100).min(255);
+        fs::write(pwm, duty_255.to_string()).map_err(Self::map_io_error)
+    }
+
+    fn map_io_error(e: std::io::Error) -> ArmouryError {
+        if e.kind() == std::io::ErrorKind::PermissionDenied {
+            ArmouryError::PermissionDenied("Cannot write fan curve (root required)".to_string())
+        } else {
+            ArmouryError::IoError(e)
+        }
+    }
+
+    /// Resample an arbitrary-length curve to exactly `count` points by linear
+    /// interpolation over the curve's own temperature range
+    fn resample_curve(points: &[FanCurvePoint], count: usize) -> Vec<FanCurvePoint> {
+        if points.is_empty() {
+            return vec![FanCurvePoint { temperature: 0, fan_percent: 0 }; count];
+        }
+        if points.len() == 1 {
+            return vec![points[0]; count];
+        }
+
+        let min_temp = points.first().unwrap().temperature as f32;
+        let max_temp = points.last().unwrap().temperature as f32;
+
+        (0..count)
+            .map(|i| {
+                let temp = if count == 1 {
+                    min_temp
                 } else {
-                    ArmouryError::IoError(e)
+                    min_temp + (max_temp - min_temp) * (i as f32 / (count - 1) as f32)
+                };
+                FanCurvePoint {
+                    temperature: temp.round() as u8,
+                    fan_percent: interpolate_fan_points(points, temp),
                 }
             })
-        } else {
-            Ok(()) // No fan curve support, nothing to reset
-        }
+            .collect()
     }
 
     // ==================== RGB Keyboard ====================
@@ -313,6 +598,74 @@ impl SysfsInterface {
         })
     }
 
+    /// Whether this model exposes a separate start threshold, beyond the
+    /// end-threshold most models support
+    pub fn has_battery_start_threshold(&self) -> bool {
+        self.battery_dir
+            .as_ref()
+            .map(|dir| dir.join(BATTERY_START_THRESHOLD_FILE).exists())
+            .unwrap_or(false)
+    }
+
+    /// Read the battery's resume-charging threshold, if the EC exposes one
+    pub fn read_battery_start_threshold(&self) -> Option<u8> {
+        let path = self.battery_dir.as_ref()?.join(BATTERY_START_THRESHOLD_FILE);
+        fs::read_to_string(path).ok()?.trim().parse().ok()
+    }
+
+    /// Write the battery's resume-charging threshold
+    pub fn write_battery_start_threshold(&self, value: u8) -> ArmouryResult<()> {
+        let path = self
+            .battery_dir
+            .as_ref()
+            .ok_or_else(|| ArmouryError::FeatureNotAvailable(
+                "Battery start threshold not available".to_string()
+            ))?
+            .join(BATTERY_START_THRESHOLD_FILE);
+
+        fs::write(&path, value.to_string()).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                ArmouryError::PermissionDenied("Cannot write battery start threshold (root required)".to_string())
+            } else {
+                ArmouryError::IoError(e)
+            }
+        })
+    }
+
+    /// Whether this model exposes a charge-rate / input-current cap
+    pub fn has_battery_charge_rate(&self) -> bool {
+        self.battery_dir
+            .as_ref()
+            .map(|dir| dir.join(BATTERY_CHARGE_RATE_FILE).exists())
+            .unwrap_or(false)
+    }
+
+    /// Read the charge-rate cap in mA, if the hardware exposes one
+    pub fn read_battery_charge_rate(&self) -> Option<u32> {
+        let path = self.battery_dir.as_ref()?.join(BATTERY_CHARGE_RATE_FILE);
+        let microamps: u32 = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+        Some(microamps / 1000)
+    }
+
+    /// Write the charge-rate cap in mA (the attribute itself is in microamps)
+    pub fn write_battery_charge_rate(&self, milliamps: u32) -> ArmouryResult<()> {
+        let path = self
+            .battery_dir
+            .as_ref()
+            .ok_or_else(|| ArmouryError::FeatureNotAvailable(
+                "Battery charge-rate cap not available".to_string()
+            ))?
+            .join(BATTERY_CHARGE_RATE_FILE);
+
+        fs::write(&path, (milliamps * 1000).to_string()).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                ArmouryError::PermissionDenied("Cannot write battery charge rate (root required)".to_string())
+            } else {
+                ArmouryError::IoError(e)
+            }
+        })
+    }
+
     /// Read battery status (percentage, AC connected)
     pub fn read_battery_status(&self) -> (u8, bool) {
         let capacity = fs::read_to_string("/sys/class/power_supply/BAT0/capacity")
@@ -335,33 +688,90 @@ impl SysfsInterface {
 
     /// Read CPU and GPU usage percentages
     pub fn read_cpu_gpu_usage(&self) -> (f32, f32) {
-        // CPU usage requires reading /proc/stat and calculating delta
-        // For simplicity, we'll read load average as an approximation
-        let cpu_usage = self.read_cpu_usage_simple().unwrap_or(0.0);
+        let cpu_usage = self.sample_cpu_usage().0;
         let gpu_usage = self.read_gpu_usage().unwrap_or(0.0);
         (cpu_usage, gpu_usage)
     }
 
-    fn read_cpu_usage_simple(&self) -> Option<f32> {
-        // Read load average as simple CPU usage indicator
-        let loadavg = fs::read_to_string("/proc/loadavg").ok()?;
-        let load: f32 = loadavg.split_whitespace().next()?.parse().ok()?;
-        
-        // Get number of CPUs
-        let cpus = std::thread::available_parallelism()
-            .map(|n| n.get() as f32)
-            .unwrap_or(1.0);
-        
-        // Convert load to percentage (capped at 100%)
-        Some((load / cpus * 100.0).min(100.0))
+    /// Sample `/proc/stat` and return (aggregate usage %, per-core usage %) since the
+    /// previous sample. The first call after startup has no prior snapshot to diff
+    /// against and reports 0.0 for every value; call this periodically (the daemon's
+    /// poll loop does) to keep the snapshot warm and the numbers meaningful.
+    pub fn sample_cpu_usage(&self) -> (f32, Vec<f32>) {
+        let Some(snapshot) = Self::read_proc_stat() else {
+            return (0.0, Vec::new());
+        };
+
+        let mut previous = self.cpu_stat.lock().unwrap();
+
+        let result = match previous.as_ref() {
+            Some(prev) => {
+                let aggregate = Self::usage_percent(&prev.aggregate, &snapshot.aggregate);
+                let per_core = snapshot
+                    .per_core
+                    .iter()
+                    .enumerate()
+                    .map(|(i, cur)| {
+                        prev.per_core
+                            .get(i)
+                            .map(|p| Self::usage_percent(p, cur))
+                            .unwrap_or(0.0)
+                    })
+                    .collect();
+                (aggregate, per_core)
+            }
+            None => (0.0, vec![0.0; snapshot.per_core.len()]),
+        };
+
+        *previous = Some(snapshot);
+        result
+    }
+
+    fn usage_percent(prev: &CpuStatFields, cur: &CpuStatFields) -> f32 {
+        let total_delta = cur.total().saturating_sub(prev.total());
+        if total_delta == 0 {
+            return 0.0;
+        }
+        let idle_delta = cur.idle_total().saturating_sub(prev.idle_total());
+        let busy_delta = total_delta.saturating_sub(idle_delta);
+        (busy_delta as f32 / total_delta as f32 * 100.0).clamp(0.0, 100.0)
+    }
+
+    fn read_proc_stat() -> Option<CpuStatSnapshot> {
+        let content = fs::read_to_string("/proc/stat").ok()?;
+        let mut aggregate = None;
+        let mut per_core = Vec::new();
+
+        for line in content.lines() {
+            let mut parts = line.split_whitespace();
+            let label = parts.next()?;
+            if label == "cpu" {
+                let fields: Vec<&str> = parts.collect();
+                aggregate = CpuStatFields::parse(&fields);
+            } else if let Some(index_str) = label.strip_prefix("cpu") {
+                if index_str.chars().all(|c| c.is_ascii_digit()) && !index_str.is_empty() {
+                    let fields: Vec<&str> = parts.collect();
+                    if let Some(core) = CpuStatFields::parse(&fields) {
+                        per_core.push(core);
+                    }
+                }
+            }
+        }
+
+        Some(CpuStatSnapshot { aggregate: aggregate?, per_core })
     }
 
     fn read_gpu_usage(&self) -> Option<f32> {
-        // Try NVIDIA GPU
-        if let Some(hwmon) = self.find_hwmon_gpu() {
-            // Some NVIDIA drivers expose GPU utilization
-            let util_path = format!("{}/gpu_busy_percent", hwmon);
-            if let Ok(util_str) = fs::read_to_string(&util_path) {
+        // Try the AMD gpu_metrics binary table first - covers APUs with no hwmon
+        if let Some(metrics) = self.read_gpu_metrics() {
+            if let Some(activity) = metrics.gfx_activity_percent {
+                return Some(activity);
+            }
+        }
+
+        // Try the hwmon path resolved at startup (some NVIDIA drivers expose this)
+        if let Some(path) = &self.hwmon.gpu_usage {
+            if let Ok(util_str) = fs::read_to_string(path) {
                 if let Ok(util) = util_str.trim().parse::<f32>() {
                     return Some(util);
                 }
@@ -381,6 +791,14 @@ impl SysfsInterface {
 
     /// Read power draw in watts
     pub fn read_power_draw(&self) -> f32 {
+        // Prefer the AMD gpu_metrics table's real-time socket power over the
+        // battery energy x voltage estimate below
+        if let Some(metrics) = self.read_gpu_metrics() {
+            if let Some(power) = metrics.socket_power_watts {
+                return power;
+            }
+        }
+
         // Try to read from battery power_now (in microwatts)
         if let Ok(power_str) = fs::read_to_string("/sys/class/power_supply/BAT0/power_now") {
             if let Ok(power) = power_str.trim().parse::<f64>() {
@@ -404,39 +822,320 @@ impl SysfsInterface {
         0.0
     }
 
-    // ==================== Helper Functions ====================
+    // ==================== AMD gpu_metrics Binary Table ====================
 
-    fn find_hwmon_cpu(&self) -> Option<String> {
-        self.find_hwmon_by_name(&["coretemp", "k10temp", "zenpower", "acpitz"])
+    /// Read and parse the versioned `gpu_metrics` binary table for the first AMD card found
+    fn read_gpu_metrics(&self) -> Option<GpuMetrics> {
+        let card = Self::find_amdgpu_card()?;
+        let data = fs::read(format!("{}/device/gpu_metrics", card)).ok()?;
+        Self::parse_gpu_metrics(&data)
     }
 
-    fn find_hwmon_gpu(&self) -> Option<String> {
-        self.find_hwmon_by_name(&["nvidia", "amdgpu", "nouveau", "radeon"])
+    /// Find the first `/sys/class/drm/cardN` directory exposing a `gpu_metrics` file
+    fn find_amdgpu_card() -> Option<String> {
+        let drm_dir = Path::new("/sys/class/drm");
+        let entries = fs::read_dir(drm_dir).ok()?;
+
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            // Skip connector nodes like "card0-DP-1"
+            if !name.starts_with("card") || name.contains('-') {
+                continue;
+            }
+
+            let path = entry.path();
+            if path.join("device/gpu_metrics").exists() {
+                return Some(path.to_string_lossy().to_string());
+            }
+        }
+
+        None
     }
 
-    fn find_hwmon_fan(&self) -> Option<String> {
-        self.find_hwmon_by_name(&["asus-nb-wmi", "asus_fan", "thinkpad"])
+    /// Parse the `metrics_table_header` (`structure_size: u16` at bytes 0-1,
+    /// `format_revision: u8` at byte 2, `content_revision: u8` at byte 3) and
+    /// dispatch to the matching fixed-offset field layout
+    fn parse_gpu_metrics(data: &[u8]) -> Option<GpuMetrics> {
+        let format_revision = *data.get(2)?;
+
+        match format_revision {
+            1 => Some(Self::parse_gpu_metrics_v1(data)),
+            2 => Some(Self::parse_gpu_metrics_v2(data)),
+            _ => {
+                debug!("Unsupported gpu_metrics format_revision: {}", format_revision);
+                None
+            }
+        }
     }
 
-    fn find_hwmon_by_name(&self, names: &[&str]) -> Option<String> {
-        let hwmon_dir = Path::new(HWMON_PATH);
-        if !hwmon_dir.exists() {
-            return None;
+    /// v1.x layout: discrete GPU, has separate edge/hotspot/vrm temperature rails
+    fn parse_gpu_metrics_v1(data: &[u8]) -> GpuMetrics {
+        GpuMetrics {
+            temp_edge: Self::gpu_metrics_centidegrees(data, 12),
+            temp_gfx: Self::gpu_metrics_centidegrees(data, 14),
+            socket_power_watts: Self::gpu_metrics_u16(data, 24),
+            gfx_clock_mhz: Self::gpu_metrics_u16(data, 26),
+            gfx_activity_percent: Self::gpu_metrics_u16(data, 32),
         }
+    }
 
-        if let Ok(entries) = fs::read_dir(hwmon_dir) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                let name_path = path.join("name");
-                if let Ok(name) = fs::read_to_string(&name_path) {
-                    let name = name.trim().to_lowercase();
-                    if names.iter().any(|n| name.contains(n)) {
-                        return Some(path.to_string_lossy().to_string());
-                    }
-                }
+    /// v2.x layout: APU, no discrete VRM rails and a smaller field set
+    fn parse_gpu_metrics_v2(data: &[u8]) -> GpuMetrics {
+        GpuMetrics {
+            temp_edge: None,
+            temp_gfx: Self::gpu_metrics_centidegrees(data, 12),
+            socket_power_watts: Self::gpu_metrics_u16(data, 18),
+            gfx_clock_mhz: Self::gpu_metrics_u16(data, 20),
+            gfx_activity_percent: Self::gpu_metrics_u16(data, 16),
+        }
+    }
+
+    /// Read a little-endian u16 field, treating the driver's `0xffff` sentinel as absent
+    fn gpu_metrics_u16(data: &[u8], offset: usize) -> Option<f32> {
+        let bytes = data.get(offset..offset + 2)?;
+        let value = u16::from_le_bytes([bytes[0], bytes[1]]);
+        if value == 0xffff {
+            None
+        } else {
+            Some(value as f32)
+        }
+    }
+
+    /// Same as `gpu_metrics_u16` but the raw value is in centidegrees Celsius
+    fn gpu_metrics_centidegrees(data: &[u8], offset: usize) -> Option<f32> {
+        Self::gpu_metrics_u16(data, offset).map(|v| v / 100.0)
+    }
+}
+
+/// Hwmon attribute paths resolved by scanning `/sys/class/hwmon/*` once at
+/// startup and matching each node's `name` file against known driver names.
+/// The `hwmonN` index order is not guaranteed stable across boots or kernel
+/// versions, so every caller uses these cached paths instead of guessing one.
+#[derive(Debug, Clone, Default)]
+struct HwmonPaths {
+    /// `tempN_input` for the matched CPU temperature driver
+    cpu_temp: Option<PathBuf>,
+    /// `tempN_input` for the matched GPU temperature driver
+    gpu_temp: Option<PathBuf>,
+    /// `gpu_busy_percent` for the matched GPU driver, where exposed
+    gpu_usage: Option<PathBuf>,
+    /// `fanN_input` whose label names the CPU fan, else `fan1_input`, for the
+    /// matched fan tachometer driver
+    cpu_fan: Option<PathBuf>,
+    /// `fanN_input` whose label names the GPU fan, else `fan2_input`, for the
+    /// matched fan tachometer driver
+    gpu_fan: Option<PathBuf>,
+    /// Directory of the `asus_custom_fan_curve` hwmon device, used for the
+    /// `pwmN_auto_pointM_{temp,pwm}` and `pwmN_enable` attributes
+    fan_curve_dir: Option<PathBuf>,
+    /// `pwm1` duty attribute alongside `cpu_fan`'s tachometer, used as a
+    /// software fallback on models with no `asus_custom_fan_curve` device
+    cpu_pwm: Option<PathBuf>,
+    /// `pwm1_enable` sibling of `cpu_pwm`, set to `1` (manual) before writing duty
+    cpu_pwm_enable: Option<PathBuf>,
+}
+
+impl HwmonPaths {
+    /// Scan `/sys/class/hwmon/*` once and resolve every known attribute path.
+    /// Any category with no matching driver is left as `None` and the caller
+    /// reports the corresponding capability as unavailable.
+    fn discover() -> Self {
+        let mut paths = Self::default();
+        let hwmon_dir = Path::new(HWMON_PATH);
+        let Ok(entries) = fs::read_dir(hwmon_dir) else {
+            warn!("{} does not exist; temperature and fan readings will be unavailable", HWMON_PATH);
+            return paths;
+        };
+
+        for entry in entries.flatten() {
+            let dir = entry.path();
+            let Ok(name) = fs::read_to_string(dir.join("name")) else {
+                continue;
+            };
+            let name = name.trim().to_lowercase();
+
+            if paths.cpu_temp.is_none() && CPU_TEMP_HWMON_NAMES.iter().any(|n| name.contains(n)) {
+                paths.cpu_temp = Self::find_labeled_channel(&dir, "temp", CPU_TEMP_LABEL_HINTS)
+                    .or_else(|| Self::existing(&dir, "temp1_input"));
+            }
+
+            if paths.gpu_temp.is_none() && GPU_HWMON_NAMES.iter().any(|n| name.contains(n)) {
+                paths.gpu_temp = Self::find_labeled_channel(&dir, "temp", GPU_TEMP_LABEL_HINTS)
+                    .or_else(|| Self::existing(&dir, "temp1_input"));
+                paths.gpu_usage = Self::existing(&dir, "gpu_busy_percent");
+            }
+
+            if paths.cpu_fan.is_none() && FAN_HWMON_NAMES.iter().any(|n| name.contains(n)) {
+                paths.cpu_fan = Self::find_labeled_channel(&dir, "fan", CPU_FAN_LABEL_HINTS)
+                    .or_else(|| Self::existing(&dir, "fan1_input"));
+                paths.gpu_fan = Self::find_labeled_channel(&dir, "fan", GPU_FAN_LABEL_HINTS)
+                    .or_else(|| Self::existing(&dir, "fan2_input"));
+                paths.cpu_pwm = Self::existing(&dir, "pwm1");
+                paths.cpu_pwm_enable = Self::existing(&dir, "pwm1_enable");
+            }
+
+            if paths.fan_curve_dir.is_none() && name.contains(FAN_CURVE_HWMON_NAME) {
+                paths.fan_curve_dir = Some(dir);
             }
         }
 
+        if paths.cpu_temp.is_none() {
+            warn!("No CPU temperature hwmon sensor found (looked for {:?})", CPU_TEMP_HWMON_NAMES);
+        }
+        if paths.gpu_temp.is_none() {
+            warn!("No GPU temperature hwmon sensor found (looked for {:?})", GPU_HWMON_NAMES);
+        }
+        if paths.cpu_fan.is_none() && paths.gpu_fan.is_none() {
+            warn!("No fan tachometer hwmon sensor found (looked for {:?})", FAN_HWMON_NAMES);
+        }
+
+        paths
+    }
+
+    /// Scan `{kind}1_label..{kind}N_label` under `dir` for one matching any of
+    /// `hints`, returning the corresponding `{kind}N_input` path. Used to pick
+    /// the right channel on multi-sensor Super-IO chips where `{kind}1` isn't
+    /// reliably the one we want.
+    fn find_labeled_channel(dir: &Path, kind: &str, hints: &[&str]) -> Option<PathBuf> {
+        for index in 1..=MAX_HWMON_CHANNEL {
+            let label_path = dir.join(format!("{}{}_label", kind, index));
+            let Ok(label) = fs::read_to_string(&label_path) else {
+                continue;
+            };
+            let label = label.trim().to_lowercase();
+            if hints.iter().any(|hint| label.contains(hint)) {
+                return Self::existing(dir, &format!("{}{}_input", kind, index));
+            }
+        }
         None
     }
+
+    fn existing(dir: &Path, attr: &str) -> Option<PathBuf> {
+        let path = dir.join(attr);
+        path.exists().then_some(path)
+    }
+}
+
+/// A single `/proc/stat` CPU line, in jiffies since boot
+#[derive(Debug, Clone, Copy, Default)]
+struct CpuStatFields {
+    user: u64,
+    nice: u64,
+    system: u64,
+    idle: u64,
+    iowait: u64,
+    irq: u64,
+    softirq: u64,
+    steal: u64,
+}
+
+impl CpuStatFields {
+    /// Parse the space-separated jiffy counters following the `cpu`/`cpuN` label
+    fn parse(fields: &[&str]) -> Option<Self> {
+        if fields.len() < 4 {
+            return None;
+        }
+        let get = |i: usize| fields.get(i).and_then(|s| s.parse::<u64>().ok()).unwrap_or(0);
+        Some(Self {
+            user: get(0),
+            nice: get(1),
+            system: get(2),
+            idle: get(3),
+            iowait: get(4),
+            irq: get(5),
+            softirq: get(6),
+            steal: get(7),
+        })
+    }
+
+    fn total(&self) -> u64 {
+        self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+    }
+
+    fn idle_total(&self) -> u64 {
+        self.idle + self.iowait
+    }
+}
+
+/// A full `/proc/stat` sample: aggregate `cpu` line plus each `cpuN` line in order
+#[derive(Debug, Clone, Default)]
+struct CpuStatSnapshot {
+    aggregate: CpuStatFields,
+    per_core: Vec<CpuStatFields>,
+}
+
+/// Telemetry extracted from the AMD `gpu_metrics` binary table
+#[derive(Debug, Clone, Copy, Default)]
+struct GpuMetrics {
+    /// GPU busy percentage (`average_gfx_activity`), 0-100
+    gfx_activity_percent: Option<f32>,
+    /// GFX/hotspot temperature in Celsius
+    temp_gfx: Option<f32>,
+    /// Edge temperature in Celsius (discrete GPUs only)
+    temp_edge: Option<f32>,
+    /// Average socket power in watts (`average_socket_power`)
+    socket_power_watts: Option<f32>,
+    /// Average graphics clock in MHz (`average_gfxclk_frequency`)
+    gfx_clock_mhz: Option<f32>,
+}
+
+#[cfg(test)]
+mod gpu_metrics_tests {
+    use super::*;
+
+    /// Builds a synthetic `gpu_metrics` blob with the given `format_revision`
+    /// and little-endian u16 fields poked in at their documented offsets.
+    fn synthetic_table(format_revision: u8, fields: &[(usize, u16)]) -> Vec<u8> {
+        let mut data = vec![0xffu8; 64];
+        data[0] = 64; // structure_size (low byte)
+        data[1] = 0; // structure_size (high byte)
+        data[2] = format_revision;
+        data[3] = 1; // content_revision
+        for &(offset, value) in fields {
+            let bytes = value.to_le_bytes();
+            data[offset] = bytes[0];
+            data[offset + 1] = bytes[1];
+        }
+        data
+    }
+
+    #[test]
+    fn parses_v1_discrete_gpu_layout() {
+        let data = synthetic_table(
+            1,
+            &[(12, 4500), (14, 6200), (24, 85), (26, 1800), (32, 42)],
+        );
+        let metrics = SysfsInterface::parse_gpu_metrics(&data).expect("v1 table should parse");
+        assert_eq!(metrics.temp_edge, Some(45.0));
+        assert_eq!(metrics.temp_gfx, Some(62.0));
+        assert_eq!(metrics.socket_power_watts, Some(85.0));
+        assert_eq!(metrics.gfx_clock_mhz, Some(1800.0));
+        assert_eq!(metrics.gfx_activity_percent, Some(42.0));
+    }
+
+    #[test]
+    fn parses_v2_apu_layout() {
+        let data = synthetic_table(2, &[(12, 5500), (16, 37), (18, 28), (20, 2100)]);
+        let metrics = SysfsInterface::parse_gpu_metrics(&data).expect("v2 table should parse");
+        assert_eq!(metrics.temp_edge, None);
+        assert_eq!(metrics.temp_gfx, Some(55.0));
+        assert_eq!(metrics.gfx_activity_percent, Some(37.0));
+        assert_eq!(metrics.socket_power_watts, Some(28.0));
+        assert_eq!(metrics.gfx_clock_mhz, Some(2100.0));
+    }
+
+    #[test]
+    fn unsupported_format_revision_returns_none() {
+        let data = synthetic_table(9, &[]);
+        assert!(SysfsInterface::parse_gpu_metrics(&data).is_none());
+    }
+
+    #[test]
+    fn sentinel_values_are_treated_as_absent() {
+        let data = synthetic_table(1, &[]);
+        let metrics = SysfsInterface::parse_gpu_metrics(&data).expect("v1 table should parse");
+        assert_eq!(metrics.temp_edge, None);
+        assert_eq!(metrics.socket_power_watts, None);
+    }
 }
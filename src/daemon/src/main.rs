@@ -11,9 +11,12 @@ mod config;
 mod hardware;
 mod dbus_server;
 mod profiles;
+mod profile_config;
+mod monitor;
 
 use config::DaemonConfig;
 use hardware::HardwareController;
+use monitor::MonitorHandle;
 use profiles::ProfileManager;
 
 /// Application state shared between D-Bus handlers
@@ -21,18 +24,30 @@ pub struct AppState {
     pub hardware: HardwareController,
     pub profiles: ProfileManager,
     pub config: DaemonConfig,
+    /// Running sensor monitor, if `start_monitoring` has been called; `None`
+    /// when stopped (the default - it's not started automatically)
+    pub monitor: Option<MonitorHandle>,
 }
 
 impl AppState {
     pub fn new() -> Result<Self> {
         let config = DaemonConfig::load()?;
-        let hardware = HardwareController::new()?;
-        let profiles = ProfileManager::new(&config)?;
+        let hardware = HardwareController::new(config.rgb_backend, config.use_supergfxctl)?;
+        let (profiles, profile_load_summary) = ProfileManager::new(&config)?;
+        if profile_load_summary.migrated > 0 || profile_load_summary.quarantined > 0 {
+            warn!(
+                "Profile load: {} loaded, {} migrated, {} quarantined",
+                profile_load_summary.loaded, profile_load_summary.migrated, profile_load_summary.quarantined
+            );
+        } else {
+            info!("Profile load: {} loaded", profile_load_summary.loaded);
+        }
 
         Ok(Self {
             hardware,
             profiles,
             config,
+            monitor: None,
         })
     }
 }
@@ -56,6 +71,7 @@ async fn main() -> Result<()> {
                 hardware: HardwareController::dummy(),
                 profiles: ProfileManager::default(),
                 config: DaemonConfig::default(),
+                monitor: None,
             }))
         }
     };
@@ -70,14 +86,198 @@ async fn main() -> Result<()> {
         info!("  Fan control: {}", caps.fan_control);
         info!("  RGB keyboard: {}", caps.rgb_keyboard);
         info!("  Battery limit: {}", caps.battery_limit);
+        if let Some(backend) = caps.tdp_backend {
+            info!("  TDP control: {}", backend);
+        }
         if let Some(model) = &caps.model_name {
             info!("  Model: {}", model);
         }
     }
 
+    // Periodically sample CPU usage so /proc/stat deltas stay meaningful between
+    // on-demand D-Bus queries
+    spawn_cpu_usage_sampler(state.clone()).await;
+
+    // Drive `RgbEffect::Temperature` gradients from live sensor readings
+    spawn_temperature_rgb_updater(state.clone()).await;
+
+    // Drive the software fan-curve fallback on models with no hardware curve device
+    spawn_fan_curve_updater(state.clone()).await;
+
+    // Evaluate auto-switch rules (AC/battery, foreground app) and switch profile
+    spawn_auto_switch_updater(state.clone()).await;
+
     // Start D-Bus server
     info!("Starting D-Bus server...");
     dbus_server::run_server(state).await?;
 
     Ok(())
 }
+
+/// Spawn a background task that evaluates the auto-switch policy engine
+/// (`ProfileManager::evaluate_auto_switch`) on the daemon's poll interval
+/// and applies whichever profile it selects. A no-op tick whenever
+/// auto-switch is disabled, the active profile is pinned, or no rule
+/// currently matches.
+async fn spawn_auto_switch_updater(state: Arc<RwLock<AppState>>) {
+    let poll_interval_ms = state.read().await.config.poll_interval_ms;
+    let interval = std::time::Duration::from_millis(poll_interval_ms.max(100));
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+
+            let mut state = state.write().await;
+            if !state.profiles.auto_switch_enabled() || state.profiles.is_pinned() {
+                continue;
+            }
+
+            let ac_connected = state.hardware.get_system_status().ac_connected;
+            let running_processes = list_running_process_names();
+
+            let Some(profile_name) = state.profiles.evaluate_auto_switch(ac_connected, &running_processes) else {
+                continue;
+            };
+
+            info!("Auto-switch: activating profile \"{}\"", profile_name);
+            if !dbus_server::apply_profile_by_name(&mut state, &profile_name) {
+                warn!("Auto-switch: failed to apply profile \"{}\"", profile_name);
+            }
+        }
+    });
+}
+
+/// List the `comm` (short name) of every process currently visible under
+/// `/proc`, for the auto-switch policy engine's `ProcessRunning` condition.
+/// Best-effort: processes that exit mid-scan or whose `comm` can't be read
+/// are silently skipped rather than failing the whole scan.
+fn list_running_process_names() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().chars().all(|c| c.is_ascii_digit()))
+        .filter_map(|entry| std::fs::read_to_string(entry.path().join("comm")).ok())
+        .map(|name| name.trim().to_string())
+        .collect()
+}
+
+/// Spawn a background task that recomputes and pushes fan duty for the
+/// software fan-curve fallback (see `HardwareController::poll_fan_curve`);
+/// a no-op tick when no curve has fallen back to software control
+async fn spawn_fan_curve_updater(state: Arc<RwLock<AppState>>) {
+    let poll_interval_ms = state.read().await.config.poll_interval_ms;
+    let interval = std::time::Duration::from_millis(poll_interval_ms.max(100));
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            state.write().await.hardware.poll_fan_curve();
+        }
+    });
+}
+
+/// Spawn a background task that keeps the CPU usage sampler's previous-snapshot
+/// window warm at the configured poll interval
+async fn spawn_cpu_usage_sampler(state: Arc<RwLock<AppState>>) {
+    let poll_interval_ms = state.read().await.config.poll_interval_ms;
+    let interval = std::time::Duration::from_millis(poll_interval_ms.max(100));
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            state.read().await.hardware.poll_cpu_usage();
+        }
+    });
+}
+
+/// Smallest per-channel change worth re-sending to the keyboard; below this
+/// we'd just be spamming the HID device with a visually-identical color
+const RGB_DEADBAND: i32 = 2;
+
+/// Spawn a background task that drives `RgbEffect::Temperature` from live
+/// CPU/GPU sensor readings, so the effect tracks sensors even with no GUI
+/// connected. Polls at the daemon's own `poll_interval_ms` cadence, but only
+/// recomputes/pushes a color once `RgbSettings::temp_poll_interval_ms` (when
+/// set) has actually elapsed, and only pushes when the result crosses the
+/// deadband from the last color sent.
+async fn spawn_temperature_rgb_updater(state: Arc<RwLock<AppState>>) {
+    let poll_interval_ms = state.read().await.config.poll_interval_ms;
+    let interval = std::time::Duration::from_millis(poll_interval_ms.max(100));
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        let mut last_applied: Option<(asus_armoury_common::RgbColor, tokio::time::Instant)> = None;
+        loop {
+            ticker.tick().await;
+            update_temperature_rgb(&state, &mut last_applied).await;
+        }
+    });
+}
+
+/// If `RgbEffect::Temperature` is active, compute the color for the current
+/// sensor reading - from `temp_gradient` if configured, else by linearly
+/// interpolating `color`/`color_secondary` across `temp_band` - and push it
+/// if enough time has passed and the color actually changed by at least
+/// [`RGB_DEADBAND`] on any channel.
+async fn update_temperature_rgb(
+    state: &Arc<RwLock<AppState>>,
+    last_applied: &mut Option<(asus_armoury_common::RgbColor, tokio::time::Instant)>,
+) {
+    use asus_armoury_common::{interpolate_gradient, interpolate_linear, RgbEffect, TempSensor};
+
+    let settings = state.read().await.hardware.get_rgb_settings();
+    if settings.effect != RgbEffect::Temperature {
+        return;
+    }
+
+    if let Some((_, applied_at)) = last_applied {
+        let poll_interval_ms = match settings.temp_poll_interval_ms {
+            Some(ms) => ms as u64,
+            None => state.read().await.config.poll_interval_ms,
+        };
+        if applied_at.elapsed() < std::time::Duration::from_millis(poll_interval_ms) {
+            return;
+        }
+    }
+
+    let (cpu_temp, gpu_temp) = state.read().await.hardware.get_temperatures();
+    let sensor_temp = match settings.temp_sensor.unwrap_or(TempSensor::Cpu) {
+        TempSensor::Cpu => cpu_temp,
+        TempSensor::Gpu => gpu_temp,
+        TempSensor::Max => cpu_temp.max(gpu_temp),
+    };
+
+    let new_color = match &settings.temp_gradient {
+        Some(stops) if !stops.is_empty() => interpolate_gradient(stops, sensor_temp),
+        _ => {
+            let Some(hot) = settings.color_secondary else {
+                return;
+            };
+            let (t_min, t_max) = settings.temp_band.unwrap_or((40, 90));
+            interpolate_linear(settings.color, hot, sensor_temp, t_min as f32, t_max as f32)
+        }
+    };
+
+    if let Some((previous, _)) = last_applied {
+        let unchanged = (new_color.r as i32 - previous.r as i32).abs() < RGB_DEADBAND
+            && (new_color.g as i32 - previous.g as i32).abs() < RGB_DEADBAND
+            && (new_color.b as i32 - previous.b as i32).abs() < RGB_DEADBAND;
+        if unchanged {
+            return;
+        }
+    }
+
+    let mut updated = settings.clone();
+    updated.color = new_color;
+
+    match state.write().await.hardware.set_rgb_settings(&updated) {
+        Ok(()) => *last_applied = Some((new_color, tokio::time::Instant::now())),
+        Err(e) => warn!("Failed to push temperature-reactive RGB color: {}", e),
+    }
+}
@@ -0,0 +1,228 @@
+//! Sensor logging / monitoring subsystem
+//!
+//! Periodically samples telemetry (temperatures, fan speeds, power draw,
+//! and the active profile name) and appends it as a timestamped row to a
+//! rotating CSV log, so profile switches can be correlated with thermals
+//! after the fact. Started/stopped on demand through the D-Bus surface
+//! rather than running unconditionally.
+
+use asus_armoury_common::{MonitorSample, SystemStatus};
+use log::{info, warn};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+
+use crate::AppState;
+
+const CSV_HEADER: &str = "timestamp_unix_ms,cpu_temp,gpu_temp,cpu_usage,gpu_usage,cpu_fan_rpm,gpu_fan_rpm,battery_percent,ac_connected,power_draw,profile\n";
+
+/// How many of the most recent samples are kept in memory for `snapshot()`,
+/// independent of what's already been flushed to the CSV log
+const SNAPSHOT_CAPACITY: usize = 512;
+
+fn sample_from_status(status: &SystemStatus, profile: String) -> MonitorSample {
+    let timestamp_unix_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis())
+        .unwrap_or(0);
+
+    MonitorSample {
+        timestamp_unix_ms,
+        cpu_temp: status.cpu_temp,
+        gpu_temp: status.gpu_temp,
+        cpu_usage: status.cpu_usage,
+        gpu_usage: status.gpu_usage,
+        cpu_fan_rpm: status.cpu_fan_rpm,
+        gpu_fan_rpm: status.gpu_fan_rpm,
+        battery_percent: status.battery_percent,
+        ac_connected: status.ac_connected,
+        power_draw: status.power_draw,
+        profile,
+    }
+}
+
+/// Quotes a CSV field if it contains a comma, quote, or newline, doubling any
+/// embedded quotes. Profile names are arbitrary user-supplied strings, so this
+/// keeps a name like `"Gaming, Loud"` from corrupting the row's column count.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_csv_row(sample: &MonitorSample) -> String {
+    format!(
+        "{},{:.1},{:.1},{:.1},{:.1},{},{},{},{},{:.2},{}\n",
+        sample.timestamp_unix_ms,
+        sample.cpu_temp,
+        sample.gpu_temp,
+        sample.cpu_usage,
+        sample.gpu_usage,
+        sample.cpu_fan_rpm,
+        sample.gpu_fan_rpm,
+        sample.battery_percent,
+        sample.ac_connected,
+        sample.power_draw,
+        csv_field(&sample.profile),
+    )
+}
+
+/// Log rotation thresholds, sourced from `DaemonConfig`
+#[derive(Debug, Clone, Copy)]
+pub struct RetentionPolicy {
+    pub max_file_bytes: u64,
+    pub max_file_age_secs: u64,
+}
+
+/// Handle to the running sampler task, held by `AppState` so `stop()` can
+/// cancel it and `snapshot()` can read back recent samples
+pub struct MonitorHandle {
+    task: JoinHandle<()>,
+    recent: Arc<RwLock<Vec<MonitorSample>>>,
+}
+
+impl MonitorHandle {
+    /// Cancel the sampler task; already-written CSV rows are left on disk
+    pub fn stop(self) {
+        self.task.abort();
+    }
+
+    /// The most recently sampled rows, oldest first
+    pub async fn snapshot(&self) -> Vec<MonitorSample> {
+        self.recent.read().await.clone()
+    }
+}
+
+/// Spawn the sampler task, appending one row to `log_dir`'s active CSV file
+/// every `sample_interval_ms`
+pub fn start(
+    state: Arc<RwLock<AppState>>,
+    log_dir: PathBuf,
+    sample_interval_ms: u64,
+    policy: RetentionPolicy,
+) -> std::io::Result<MonitorHandle> {
+    fs::create_dir_all(&log_dir)?;
+
+    let recent = Arc::new(RwLock::new(Vec::with_capacity(SNAPSHOT_CAPACITY)));
+    let recent_task = recent.clone();
+
+    let task = tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_millis(sample_interval_ms.max(100)));
+        loop {
+            ticker.tick().await;
+
+            let (status, profile) = {
+                let guard = state.read().await;
+                (
+                    guard.hardware.get_system_status(),
+                    guard.profiles.current_profile_name().to_string(),
+                )
+            };
+            let sample = sample_from_status(&status, profile);
+
+            if let Err(e) = append_row(&log_dir, &sample, &policy) {
+                warn!("Failed to write sensor monitor sample: {}", e);
+            }
+
+            let mut recent = recent_task.write().await;
+            recent.push(sample);
+            let overflow = recent.len().saturating_sub(SNAPSHOT_CAPACITY);
+            if overflow > 0 {
+                recent.drain(0..overflow);
+            }
+        }
+    });
+
+    info!("Sensor monitoring started, logging to {}", log_dir.display());
+    Ok(MonitorHandle { task, recent })
+}
+
+fn active_log_path(log_dir: &Path) -> PathBuf {
+    log_dir.join("sensors.csv")
+}
+
+fn append_row(log_dir: &Path, sample: &MonitorSample, policy: &RetentionPolicy) -> std::io::Result<()> {
+    let path = active_log_path(log_dir);
+    rotate_if_needed(&path, policy)?;
+
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    if is_new {
+        file.write_all(CSV_HEADER.as_bytes())?;
+    }
+    file.write_all(to_csv_row(sample).as_bytes())
+}
+
+/// Rotate `path` to a timestamped sibling once it crosses either threshold
+/// in `policy`. A no-op if the file doesn't exist yet.
+fn rotate_if_needed(path: &Path, policy: &RetentionPolicy) -> std::io::Result<()> {
+    let Ok(metadata) = fs::metadata(path) else {
+        return Ok(());
+    };
+
+    let too_big = metadata.len() >= policy.max_file_bytes;
+    // `created()` isn't supported on every filesystem; if it's unavailable
+    // we just skip the age check rather than rotating on every write
+    let too_old = metadata
+        .created()
+        .ok()
+        .and_then(|created| created.elapsed().ok())
+        .map(|age| age.as_secs() >= policy.max_file_age_secs)
+        .unwrap_or(false);
+
+    if !too_big && !too_old {
+        return Ok(());
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let rotated = path.with_file_name(format!("sensors-{}.csv", timestamp));
+    fs::rename(path, rotated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_profile_name_is_unquoted() {
+        assert_eq!(csv_field("Performance"), "Performance");
+    }
+
+    #[test]
+    fn profile_name_with_comma_is_quoted() {
+        assert_eq!(csv_field("Gaming, Loud"), "\"Gaming, Loud\"");
+    }
+
+    #[test]
+    fn embedded_quotes_are_doubled() {
+        assert_eq!(csv_field("The \"Loud\" One"), "\"The \"\"Loud\"\" One\"");
+    }
+
+    #[test]
+    fn csv_row_quotes_comma_in_profile_name() {
+        let sample = MonitorSample {
+            timestamp_unix_ms: 1,
+            cpu_temp: 50.0,
+            gpu_temp: 40.0,
+            cpu_usage: 10.0,
+            gpu_usage: 5.0,
+            cpu_fan_rpm: 2000,
+            gpu_fan_rpm: 1800,
+            battery_percent: 90,
+            ac_connected: true,
+            power_draw: 15.25,
+            profile: "Gaming, Loud".to_string(),
+        };
+        let row = to_csv_row(&sample);
+        assert!(row.trim_end().ends_with("\"Gaming, Loud\""));
+    }
+}
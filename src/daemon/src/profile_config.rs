@@ -0,0 +1,172 @@
+//! Layered profile configuration
+//!
+//! Mirrors the layered-config approach used by tools like bottom: start from
+//! a base `Profile`, overlay whatever the on-disk partial config file
+//! specifies, then overlay any runtime/CLI overrides. Each layer only sets
+//! the fields it cares about, so the file doesn't need to restate the whole
+//! profile.
+
+use asus_armoury_common::{
+    ArmouryError, ArmouryResult, BatterySettings, FanCurve, FanMode, GpuMode, PerformanceMode,
+    Profile, RgbSettings, TdpSettings,
+};
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+/// A `Profile` with every field optional, for partial overlays from the
+/// config file or runtime/CLI flags
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PartialProfile {
+    pub name: Option<String>,
+    pub performance_mode: Option<PerformanceMode>,
+    pub gpu_mode: Option<GpuMode>,
+    pub fan_mode: Option<FanMode>,
+    pub fan_curve: Option<FanCurve>,
+    pub rgb_settings: Option<RgbSettings>,
+    pub battery_settings: Option<BatterySettings>,
+    pub tdp_settings: Option<TdpSettings>,
+}
+
+impl PartialProfile {
+    /// Overlay the fields this layer specifies onto `base`, leaving fields
+    /// it doesn't mention untouched
+    pub fn apply_over(&self, mut base: Profile) -> Profile {
+        if let Some(name) = &self.name {
+            base.name = name.clone();
+        }
+        if let Some(mode) = self.performance_mode {
+            base.performance_mode = mode;
+        }
+        if let Some(mode) = self.gpu_mode {
+            base.gpu_mode = mode;
+        }
+        if let Some(mode) = self.fan_mode {
+            base.fan_mode = mode;
+        }
+        if let Some(curve) = &self.fan_curve {
+            base.fan_curve = Some(curve.clone());
+        }
+        if let Some(rgb) = &self.rgb_settings {
+            base.rgb_settings = rgb.clone();
+        }
+        if let Some(battery) = self.battery_settings {
+            base.battery_settings = battery;
+        }
+        if let Some(tdp) = self.tdp_settings {
+            base.tdp_settings = Some(tdp);
+        }
+        base
+    }
+}
+
+/// Resolve the effective profile: `base` overlaid with the on-disk partial
+/// config (if any), then with runtime/CLI `overrides`
+pub fn resolve_profile(base: Profile, overrides: Option<&PartialProfile>) -> ArmouryResult<Profile> {
+    let mut profile = base;
+
+    if let Some(file_layer) = load_partial_profile()? {
+        profile = file_layer.apply_over(profile);
+    }
+    if let Some(overrides) = overrides {
+        profile = overrides.apply_over(profile);
+    }
+
+    Ok(profile)
+}
+
+/// Load the on-disk partial profile layer, if a config file exists
+fn load_partial_profile() -> ArmouryResult<Option<PartialProfile>> {
+    let path = partial_profile_path();
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path)?;
+    let partial: PartialProfile = toml::from_str(&content).map_err(|e| {
+        ArmouryError::ConfigError(format!("Failed to parse {}: {}", path.display(), e))
+    })?;
+    Ok(Some(partial))
+}
+
+/// Persist a partial profile layer to disk for the next startup to pick up
+pub fn save_partial_profile(partial: &PartialProfile) -> ArmouryResult<()> {
+    let path = partial_profile_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let content = toml::to_string_pretty(partial).map_err(|e| {
+        ArmouryError::ConfigError(format!("Failed to serialize profile config: {}", e))
+    })?;
+    fs::write(&path, content)?;
+    Ok(())
+}
+
+fn partial_profile_path() -> PathBuf {
+    if let Some(proj_dirs) = ProjectDirs::from("org", "asuslinux", "armoury") {
+        proj_dirs.config_dir().join("profile.toml")
+    } else {
+        PathBuf::from("/etc/asus-armoury/profile.toml")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use asus_armoury_common::{FanCurvePoint, RgbColor, RgbEffect, TempSensor};
+
+    #[test]
+    fn empty_overrides_leave_base_untouched() {
+        let base = Profile::default();
+        let resolved = PartialProfile::default().apply_over(base.clone());
+        assert_eq!(resolved.name, base.name);
+        assert_eq!(resolved.performance_mode, base.performance_mode);
+    }
+
+    #[test]
+    fn overlay_only_touches_specified_fields() {
+        let base = Profile::default();
+        let partial = PartialProfile {
+            performance_mode: Some(PerformanceMode::Turbo),
+            ..Default::default()
+        };
+        let resolved = partial.apply_over(base.clone());
+        assert_eq!(resolved.performance_mode, PerformanceMode::Turbo);
+        assert_eq!(resolved.gpu_mode, base.gpu_mode);
+        assert_eq!(resolved.rgb_settings, base.rgb_settings);
+    }
+
+    #[test]
+    fn round_trips_through_toml_including_fan_curve_and_rgb() {
+        let partial = PartialProfile {
+            name: Some("Custom".to_string()),
+            fan_curve: Some(FanCurve {
+                name: "Custom".to_string(),
+                points: vec![
+                    FanCurvePoint { temperature: 40, fan_percent: 20 },
+                    FanCurvePoint { temperature: 80, fan_percent: 100 },
+                ],
+            }),
+            rgb_settings: Some(RgbSettings {
+                effect: RgbEffect::Temperature,
+                color: RgbColor::new(10, 20, 30),
+                color_secondary: Some(RgbColor::new(40, 50, 60)),
+                brightness: 80,
+                speed: 30,
+                temp_sensor: Some(TempSensor::Gpu),
+                temp_band: Some((45, 95)),
+                temp_gradient: None,
+                zone_colors: None,
+                temp_poll_interval_ms: None,
+            }),
+            ..Default::default()
+        };
+
+        let serialized = toml::to_string_pretty(&partial).unwrap();
+        let deserialized: PartialProfile = toml::from_str(&serialized).unwrap();
+
+        assert_eq!(deserialized, partial);
+    }
+}
@@ -1,12 +1,28 @@
 //! Profile management for storing and loading user profiles
 
-use asus_armoury_common::{ArmouryResult, ArmouryError, Profile, PerformanceMode, GpuMode, FanMode};
+use asus_armoury_common::{
+    ArmouryResult, ArmouryError, AutoSwitchCondition, AutoSwitchRule, CURRENT_PROFILE_SCHEMA_VERSION,
+    FanMode, GpuMode, PerformanceMode, Profile, ProfileLoadSummary, ProfileVariant, VariantInfo,
+};
 use log::{info, warn};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::config::DaemonConfig;
+use crate::profile_config::resolve_profile;
+
+/// The auto-switch rule set and enable flag, persisted alongside the
+/// profiles themselves
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct AutoSwitchConfig {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    rules: Vec<AutoSwitchRule>,
+}
 
 /// Profile manager handling loading, saving, and applying profiles
 pub struct ProfileManager {
@@ -16,6 +32,15 @@ pub struct ProfileManager {
     profiles: HashMap<String, Profile>,
     /// Currently active profile name
     current_profile: String,
+    /// Auto-switch rule set and enable flag
+    auto_switch: AutoSwitchConfig,
+    /// Set whenever the user explicitly applies a profile or variant; the
+    /// auto-switch policy engine leaves the active profile alone while this
+    /// is set, until `unpin()` is called
+    pinned: bool,
+    /// Result of the most recent `load_profiles` pass, so the GUI can report
+    /// quarantined or migrated files to the user after startup
+    load_summary: ProfileLoadSummary,
 }
 
 impl Default for ProfileManager {
@@ -24,6 +49,9 @@ impl Default for ProfileManager {
             profiles_dir: PathBuf::from("/tmp/asus-armoury/profiles"),
             profiles: HashMap::new(),
             current_profile: "Balanced".to_string(),
+            auto_switch: AutoSwitchConfig::default(),
+            pinned: false,
+            load_summary: ProfileLoadSummary::default(),
         };
         manager.create_default_profiles();
         manager
@@ -31,10 +59,12 @@ impl Default for ProfileManager {
 }
 
 impl ProfileManager {
-    /// Create a new profile manager
-    pub fn new(config: &DaemonConfig) -> ArmouryResult<Self> {
+    /// Create a new profile manager, returning it alongside a summary of how
+    /// many profiles loaded cleanly, were migrated from an older schema, or
+    /// were quarantined after failing to parse
+    pub fn new(config: &DaemonConfig) -> ArmouryResult<(Self, ProfileLoadSummary)> {
         let profiles_dir = config.profiles_dir.clone();
-        
+
         // Ensure profiles directory exists
         if !profiles_dir.exists() {
             fs::create_dir_all(&profiles_dir)?;
@@ -44,6 +74,9 @@ impl ProfileManager {
             profiles_dir,
             profiles: HashMap::new(),
             current_profile: config.default_profile.clone(),
+            auto_switch: AutoSwitchConfig::default(),
+            pinned: false,
+            load_summary: ProfileLoadSummary::default(),
         };
 
         // Load existing profiles
@@ -53,15 +86,31 @@ impl ProfileManager {
         if manager.profiles.is_empty() {
             manager.create_default_profiles();
             manager.save_all_profiles()?;
+        } else {
+            // Re-apply the profile.toml file layer (and any future runtime
+            // overrides) over the on-disk Balanced profile on every startup,
+            // not just this first-run seeding path, so editing profile.toml
+            // after the first run still takes effect
+            manager.reresolve_balanced_profile();
         }
 
-        Ok(manager)
+        manager.auto_switch = manager.load_auto_switch();
+
+        let summary = manager.load_summary.clone();
+        Ok((manager, summary))
+    }
+
+    /// Summary of the most recent `load_profiles` pass (from construction),
+    /// so the GUI can report quarantined or migrated files after the fact
+    pub fn load_summary(&self) -> &ProfileLoadSummary {
+        &self.load_summary
     }
 
     /// Create default profiles
     fn create_default_profiles(&mut self) {
         // Gaming profile
         let gaming = Profile {
+            version: asus_armoury_common::CURRENT_PROFILE_SCHEMA_VERSION,
             name: "Gaming".to_string(),
             performance_mode: PerformanceMode::Turbo,
             gpu_mode: GpuMode::Dedicated,
@@ -73,12 +122,21 @@ impl ProfileManager {
                 color_secondary: None,
                 brightness: 100,
                 speed: 75,
+                temp_sensor: None,
+                temp_band: None,
+                temp_gradient: None,
+                zone_colors: None,
+                temp_poll_interval_ms: None,
             },
-            battery_settings: asus_armoury_common::BatterySettings { charge_limit: 100 },
+            battery_settings: asus_armoury_common::BatterySettings { charge_control_start_threshold: 0, charge_control_end_threshold: 100, charge_rate_ma: None },
+            tdp_settings: None,
+            variants: Vec::new(),
+            default_variant_id: None,
         };
 
         // Work profile
         let work = Profile {
+            version: asus_armoury_common::CURRENT_PROFILE_SCHEMA_VERSION,
             name: "Work".to_string(),
             performance_mode: PerformanceMode::Balanced,
             gpu_mode: GpuMode::Integrated,
@@ -90,12 +148,21 @@ impl ProfileManager {
                 color_secondary: None,
                 brightness: 50,
                 speed: 50,
+                temp_sensor: None,
+                temp_band: None,
+                temp_gradient: None,
+                zone_colors: None,
+                temp_poll_interval_ms: None,
             },
-            battery_settings: asus_armoury_common::BatterySettings { charge_limit: 80 },
+            battery_settings: asus_armoury_common::BatterySettings { charge_control_start_threshold: 0, charge_control_end_threshold: 80, charge_rate_ma: None },
+            tdp_settings: None,
+            variants: Vec::new(),
+            default_variant_id: None,
         };
 
         // Silent profile
         let silent = Profile {
+            version: asus_armoury_common::CURRENT_PROFILE_SCHEMA_VERSION,
             name: "Silent".to_string(),
             performance_mode: PerformanceMode::Silent,
             gpu_mode: GpuMode::Integrated,
@@ -107,20 +174,37 @@ impl ProfileManager {
                 color_secondary: None,
                 brightness: 0,
                 speed: 0,
+                temp_sensor: None,
+                temp_band: None,
+                temp_gradient: None,
+                zone_colors: None,
+                temp_poll_interval_ms: None,
             },
-            battery_settings: asus_armoury_common::BatterySettings { charge_limit: 60 },
+            battery_settings: asus_armoury_common::BatterySettings { charge_control_start_threshold: 0, charge_control_end_threshold: 60, charge_rate_ma: None },
+            tdp_settings: None,
+            variants: Vec::new(),
+            default_variant_id: None,
         };
 
-        // Balanced profile
-        let balanced = Profile {
+        // Balanced profile; this is the one layer a user's on-disk
+        // ~/.config/.../profile.toml (and any runtime overrides) can tweak
+        let balanced_base = Profile {
+            version: asus_armoury_common::CURRENT_PROFILE_SCHEMA_VERSION,
             name: "Balanced".to_string(),
             performance_mode: PerformanceMode::Balanced,
             gpu_mode: GpuMode::Hybrid,
             fan_mode: FanMode::Auto,
             fan_curve: None,
             rgb_settings: asus_armoury_common::RgbSettings::default(),
-            battery_settings: asus_armoury_common::BatterySettings { charge_limit: 100 },
+            battery_settings: asus_armoury_common::BatterySettings { charge_control_start_threshold: 0, charge_control_end_threshold: 100, charge_rate_ma: None },
+            tdp_settings: None,
+            variants: Vec::new(),
+            default_variant_id: None,
         };
+        let balanced = resolve_profile(balanced_base.clone(), None).unwrap_or_else(|e| {
+            warn!("Failed to resolve layered profile config, using built-in defaults: {}", e);
+            balanced_base
+        });
 
         self.profiles.insert("Gaming".to_string(), gaming);
         self.profiles.insert("Work".to_string(), work);
@@ -128,6 +212,25 @@ impl ProfileManager {
         self.profiles.insert("Balanced".to_string(), balanced);
     }
 
+    /// Re-overlay the `profile.toml` file layer onto the on-disk Balanced
+    /// profile. Called on every startup (not just first-run seeding in
+    /// `create_default_profiles`), so edits to `profile.toml` keep taking
+    /// effect once `Balanced.json` already exists on disk
+    fn reresolve_balanced_profile(&mut self) {
+        let Some(balanced) = self.profiles.get("Balanced").cloned() else {
+            return;
+        };
+
+        match resolve_profile(balanced, None) {
+            Ok(resolved) => {
+                self.profiles.insert("Balanced".to_string(), resolved);
+            }
+            Err(e) => {
+                warn!("Failed to resolve layered profile config, keeping on-disk Balanced profile: {}", e);
+            }
+        }
+    }
+
     /// Load profiles from disk
     fn load_profiles(&mut self) -> ArmouryResult<()> {
         if !self.profiles_dir.exists() {
@@ -135,15 +238,43 @@ impl ProfileManager {
         }
 
         let entries = fs::read_dir(&self.profiles_dir)?;
-        
+
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().map(|e| e == "json").unwrap_or(false) {
-                if let Ok(content) = fs::read_to_string(&path) {
-                    if let Ok(profile) = serde_json::from_str::<Profile>(&content) {
+            let is_profile_json = path.extension().map(|e| e == "json").unwrap_or(false)
+                && path.file_name().map(|n| n != Self::auto_switch_file_name()).unwrap_or(false);
+            if !is_profile_json {
+                continue;
+            }
+
+            let content = match fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(e) => {
+                    warn!("Failed to read profile file {}: {}", path.display(), e);
+                    continue;
+                }
+            };
+
+            match Self::parse_profile(&content) {
+                Ok((profile, migrated)) => {
+                    if migrated {
+                        info!("Migrated profile \"{}\" to schema version {}", profile.name, CURRENT_PROFILE_SCHEMA_VERSION);
+                        if let Err(e) = self.save_profile_to_disk(&profile) {
+                            warn!("Failed to re-save migrated profile \"{}\": {}", profile.name, e);
+                        }
+                        self.load_summary.migrated += 1;
+                    } else {
                         info!("Loaded profile: {}", profile.name);
-                        self.profiles.insert(profile.name.clone(), profile);
+                        self.load_summary.loaded += 1;
                     }
+                    self.profiles.insert(profile.name.clone(), profile);
+                }
+                Err(e) => {
+                    warn!("Profile file {} failed to parse ({}), quarantining", path.display(), e);
+                    if let Err(qe) = Self::quarantine_file(&self.profiles_dir, &path) {
+                        warn!("Failed to quarantine {}: {}", path.display(), qe);
+                    }
+                    self.load_summary.quarantined += 1;
                 }
             }
         }
@@ -151,6 +282,60 @@ impl ProfileManager {
         Ok(())
     }
 
+    /// Name of the auto-switch rule file, excluded from the profile-loading
+    /// scan of `profiles_dir` even though it shares the `.json` extension
+    fn auto_switch_file_name() -> &'static std::ffi::OsStr {
+        std::ffi::OsStr::new("auto_switch.json")
+    }
+
+    /// Parse a profile file's contents, migrating it to the current schema
+    /// first if its `version` is older. Returns whether a migration ran, so
+    /// the caller knows to re-save the upgraded file.
+    fn parse_profile(content: &str) -> Result<(Profile, bool), serde_json::Error> {
+        let mut value: serde_json::Value = serde_json::from_str(content)?;
+        let version = value.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+        let migrated = version < CURRENT_PROFILE_SCHEMA_VERSION as u64;
+
+        if migrated {
+            Self::migrate_profile_value(&mut value, version);
+        }
+
+        let profile: Profile = serde_json::from_value(value)?;
+        Ok((profile, migrated))
+    }
+
+    /// Backfill fields added to `Profile` since `from_version`, so
+    /// `serde_json::from_value` can parse an older file into the current
+    /// struct shape, then stamp the value with the current schema version
+    fn migrate_profile_value(value: &mut serde_json::Value, from_version: u64) {
+        if let Some(obj) = value.as_object_mut() {
+            if from_version < 1 {
+                // Variants/default_variant_id were added in schema version 1;
+                // older files predate them entirely
+                obj.entry("variants").or_insert_with(|| serde_json::Value::Array(Vec::new()));
+                obj.entry("default_variant_id").or_insert(serde_json::Value::Null);
+            }
+            obj.insert("version".to_string(), serde_json::Value::from(CURRENT_PROFILE_SCHEMA_VERSION));
+        }
+    }
+
+    /// Move a file that failed to parse into a `.corrupt` subdirectory of
+    /// `profiles_dir`, tagged with the time it was quarantined, instead of
+    /// discarding it silently
+    fn quarantine_file(profiles_dir: &Path, path: &Path) -> std::io::Result<()> {
+        let quarantine_dir = profiles_dir.join(".corrupt");
+        fs::create_dir_all(&quarantine_dir)?;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_else(|| "profile.json".to_string());
+        let dest = quarantine_dir.join(format!("{}.{}.corrupt", file_name, timestamp));
+
+        fs::rename(path, dest)
+    }
+
     /// Save all profiles to disk
     fn save_all_profiles(&self) -> ArmouryResult<()> {
         for profile in self.profiles.values() {
@@ -168,6 +353,111 @@ impl ProfileManager {
         Ok(())
     }
 
+    /// Path of the auto-switch rule set file, stored alongside the profiles
+    fn auto_switch_path(&self) -> PathBuf {
+        self.profiles_dir.join("auto_switch.json")
+    }
+
+    /// Load the auto-switch rule set from disk, falling back to disabled
+    /// with no rules if it doesn't exist or fails to parse
+    fn load_auto_switch(&self) -> AutoSwitchConfig {
+        let path = self.auto_switch_path();
+        fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persist the auto-switch rule set to disk
+    fn save_auto_switch(&self) -> ArmouryResult<()> {
+        let content = serde_json::to_string_pretty(&self.auto_switch)
+            .map_err(|e| ArmouryError::ConfigError(format!("Failed to serialize auto-switch rules: {}", e)))?;
+        fs::write(self.auto_switch_path(), content)?;
+        Ok(())
+    }
+
+    /// Whether the auto-switch policy engine is enabled
+    pub fn auto_switch_enabled(&self) -> bool {
+        self.auto_switch.enabled
+    }
+
+    /// Enable or disable the auto-switch policy engine
+    pub fn set_auto_switch_enabled(&mut self, enabled: bool) -> ArmouryResult<()> {
+        self.auto_switch.enabled = enabled;
+        self.save_auto_switch()
+    }
+
+    /// List the auto-switch rules, in evaluation order
+    pub fn list_auto_switch_rules(&self) -> &[AutoSwitchRule] {
+        &self.auto_switch.rules
+    }
+
+    /// Add a new auto-switch rule, evaluated after any existing ones
+    pub fn add_auto_switch_rule(&mut self, condition: AutoSwitchCondition, profile_name: String) -> ArmouryResult<u64> {
+        if !self.profiles.contains_key(&profile_name) {
+            return Err(ArmouryError::InvalidValue(format!("Profile not found: {}", profile_name)));
+        }
+        let id = self.auto_switch.rules.iter().map(|r| r.id).max().unwrap_or(0) + 1;
+        self.auto_switch.rules.push(AutoSwitchRule { id, condition, profile_name });
+        self.save_auto_switch()?;
+        Ok(id)
+    }
+
+    /// Remove an auto-switch rule by id
+    pub fn remove_auto_switch_rule(&mut self, id: u64) -> ArmouryResult<()> {
+        let before = self.auto_switch.rules.len();
+        self.auto_switch.rules.retain(|r| r.id != id);
+        if self.auto_switch.rules.len() == before {
+            return Err(ArmouryError::InvalidValue(format!("Auto-switch rule not found: {}", id)));
+        }
+        self.save_auto_switch()
+    }
+
+    /// Pin the current profile, so the auto-switch policy engine leaves it
+    /// alone; set automatically whenever a profile is applied manually
+    pub fn pin(&mut self) {
+        self.pinned = true;
+    }
+
+    /// Unpin the current profile, letting the auto-switch policy engine
+    /// resume switching on the next rule evaluation
+    pub fn unpin(&mut self) {
+        self.pinned = false;
+    }
+
+    /// Whether the current profile is pinned against auto-switching
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    /// Evaluate the auto-switch rules in order against the current power
+    /// state and running processes, returning the first matching rule's
+    /// profile name if it differs from the one already active. Returns
+    /// `None` if auto-switch is disabled, the profile is pinned, or nothing
+    /// matches (or the match is already current).
+    pub fn evaluate_auto_switch(&self, ac_connected: bool, running_processes: &[String]) -> Option<String> {
+        if !self.auto_switch.enabled || self.pinned {
+            return None;
+        }
+
+        for rule in &self.auto_switch.rules {
+            let matches = match &rule.condition {
+                AutoSwitchCondition::OnAcPower => ac_connected,
+                AutoSwitchCondition::OnBatteryPower => !ac_connected,
+                AutoSwitchCondition::ProcessRunning(names) => names.iter().any(|name| {
+                    let name = name.to_lowercase();
+                    running_processes.iter().any(|p| p.to_lowercase().contains(&name))
+                }),
+            };
+
+            if matches {
+                return (rule.profile_name != self.current_profile).then(|| rule.profile_name.clone());
+            }
+        }
+
+        None
+    }
+
     /// List all available profiles
     pub fn list_profiles(&self) -> Vec<&Profile> {
         self.profiles.values().collect()
@@ -190,14 +480,74 @@ impl ProfileManager {
         }
     }
 
-    /// Save or update a profile
+    /// Save or update a profile, rejecting an invalid fan curve (on the
+    /// profile itself or any of its variants) before it ever reaches disk
     pub fn save_profile(&mut self, profile: Profile) -> ArmouryResult<()> {
+        if let Some(curve) = &profile.fan_curve {
+            curve.validate()?;
+        }
+        for variant in &profile.variants {
+            if let Some(curve) = &variant.fan_curve {
+                curve.validate()?;
+            }
+        }
+
         let name = profile.name.clone();
         self.save_profile_to_disk(&profile)?;
         self.profiles.insert(name, profile);
         Ok(())
     }
 
+    /// List a profile's variants, without their settings payload
+    pub fn list_variants(&self, profile_name: &str) -> Vec<VariantInfo> {
+        self.profiles
+            .get(profile_name)
+            .map(|p| p.variants.iter().map(|v| v.info.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Look up a single variant of a profile by id
+    pub fn get_variant(&self, profile_name: &str, variant_id: u64) -> Option<ProfileVariant> {
+        self.profiles
+            .get(profile_name)?
+            .variants
+            .iter()
+            .find(|v| v.info.id == variant_id)
+            .cloned()
+    }
+
+    /// The variant a profile resolves to when applied without naming one
+    /// explicitly: `default_variant_id` if it still exists, else the first
+    /// entry in `variants`, else `None` if the profile has no variants
+    pub fn default_variant(&self, profile_name: &str) -> Option<ProfileVariant> {
+        let profile = self.profiles.get(profile_name)?;
+        if let Some(id) = profile.default_variant_id {
+            if let Some(variant) = profile.variants.iter().find(|v| v.info.id == id) {
+                return Some(variant.clone());
+            }
+        }
+        profile.variants.first().cloned()
+    }
+
+    /// Add or replace (by id) a variant on a profile, then persist the profile
+    pub fn save_variant(&mut self, profile_name: &str, variant: ProfileVariant) -> ArmouryResult<()> {
+        if let Some(curve) = &variant.fan_curve {
+            curve.validate()?;
+        }
+
+        let profile = self.profiles.get_mut(profile_name).ok_or_else(|| {
+            ArmouryError::InvalidValue(format!("Profile not found: {}", profile_name))
+        })?;
+
+        match profile.variants.iter_mut().find(|v| v.info.id == variant.info.id) {
+            Some(existing) => *existing = variant,
+            None => profile.variants.push(variant),
+        }
+
+        let profile = profile.clone();
+        self.save_profile_to_disk(&profile)
+    }
+
     /// Delete a profile
     pub fn delete_profile(&mut self, name: &str) -> ArmouryResult<()> {
         // Don't delete default profiles
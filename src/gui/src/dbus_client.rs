@@ -2,7 +2,9 @@
 
 use asus_armoury_common::{
     dbus_interface::{DBUS_NAME, DBUS_PATH},
-    HardwareCapabilities, Profile, RgbSettings, SystemStatus,
+    AutoSwitchCondition, AutoSwitchRule, BatterySettings, HardwareCapabilities, MonitorSample,
+    Profile, ProfileLoadSummary, ProfileVariant, RgbSettings, SettingsLimits, SystemStatus,
+    TdpSettings, VariantInfo,
 };
 use log::{error, info};
 use zbus::{proxy, Connection, Result};
@@ -16,32 +18,67 @@ use zbus::{proxy, Connection, Result};
 trait Armoury {
     fn version(&self) -> Result<String>;
     fn get_capabilities(&self) -> Result<String>;
-    fn get_system_status(&self) -> Result<String>;
-    
-    fn get_performance_mode(&self) -> Result<String>;
+    fn get_limits(&self) -> Result<String>;
+
+    #[zbus(property)]
+    fn system_status(&self) -> Result<String>;
+
+    /// Emitted whenever telemetry moves past a noise threshold, carrying the
+    /// serialized `SystemStatus` - subscribe instead of polling `system_status`
+    #[zbus(signal)]
+    fn status_changed(&self, status: String) -> Result<()>;
+
+    #[zbus(property)]
+    fn performance_mode(&self) -> Result<String>;
     fn set_performance_mode(&self, mode: &str) -> Result<bool>;
-    
-    fn get_gpu_mode(&self) -> Result<String>;
+
+    #[zbus(property)]
+    fn gpu_mode(&self) -> Result<String>;
     fn set_gpu_mode(&self, mode: &str) -> Result<bool>;
-    
-    fn get_fan_speeds(&self) -> Result<String>;
+
+    #[zbus(property)]
+    fn fan_speeds(&self) -> Result<String>;
     fn set_fan_curve(&self, curve_json: &str) -> Result<bool>;
     fn reset_fan_auto(&self) -> Result<bool>;
-    
-    fn get_temperatures(&self) -> Result<String>;
-    
+
+    #[zbus(property)]
+    fn temperatures(&self) -> Result<String>;
+
     fn get_rgb_settings(&self) -> Result<String>;
     fn set_rgb_settings(&self, settings_json: &str) -> Result<bool>;
     
     fn get_battery_limit(&self) -> Result<u8>;
     fn set_battery_limit(&self, limit: u8) -> Result<bool>;
-    
+    fn get_battery_settings(&self) -> Result<String>;
+    fn set_battery_settings(&self, settings_json: &str) -> Result<bool>;
+
+    fn get_tdp(&self) -> Result<String>;
+    fn set_tdp(&self, settings_json: &str) -> Result<bool>;
+
     fn list_profiles(&self) -> Result<String>;
     fn get_current_profile(&self) -> Result<String>;
     fn get_profile(&self, name: &str) -> Result<String>;
+    fn profile_load_summary(&self) -> Result<String>;
     fn apply_profile(&self, name: &str) -> Result<bool>;
     fn save_profile(&self, profile_json: &str) -> Result<bool>;
     fn delete_profile(&self, name: &str) -> Result<bool>;
+
+    fn list_variants(&self, profile_name: &str) -> Result<String>;
+    fn apply_variant(&self, profile_name: &str, variant_id: u64) -> Result<bool>;
+    fn save_variant(&self, profile_name: &str, variant_json: &str) -> Result<bool>;
+
+    fn start_monitoring(&self) -> Result<bool>;
+    fn stop_monitoring(&self) -> Result<bool>;
+    fn is_monitoring(&self) -> Result<bool>;
+    fn monitoring_snapshot(&self) -> Result<String>;
+
+    fn auto_switch_enabled(&self) -> Result<bool>;
+    fn set_auto_switch_enabled(&self, enabled: bool) -> Result<bool>;
+    fn list_auto_switch_rules(&self) -> Result<String>;
+    fn add_auto_switch_rule(&self, condition_json: &str, profile_name: &str) -> Result<u64>;
+    fn remove_auto_switch_rule(&self, id: u64) -> Result<bool>;
+    fn unpin_profile(&self) -> Result<()>;
+    fn is_profile_pinned(&self) -> Result<bool>;
 }
 
 /// Client wrapper for easier interaction with the daemon
@@ -90,15 +127,27 @@ impl DaemonClient {
         serde_json::from_str(&json).ok()
     }
 
+    /// Get the detected model's battery/fan/TDP setting ranges
+    pub async fn get_limits(&self) -> Option<SettingsLimits> {
+        let json = self.proxy.as_ref()?.get_limits().await.ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
     /// Get system status
     pub async fn get_system_status(&self) -> Option<SystemStatus> {
-        let json = self.proxy.as_ref()?.get_system_status().await.ok()?;
+        let json = self.proxy.as_ref()?.system_status().await.ok()?;
         serde_json::from_str(&json).ok()
     }
 
+    /// Subscribe to push `status_changed` updates instead of polling
+    /// `get_system_status`. Returns `None` if not connected to the daemon.
+    pub async fn receive_status_changed(&self) -> Option<StatusChangedStream<'static>> {
+        self.proxy.as_ref()?.receive_status_changed().await.ok()
+    }
+
     /// Get current performance mode
     pub async fn get_performance_mode(&self) -> Option<String> {
-        self.proxy.as_ref()?.get_performance_mode().await.ok()
+        self.proxy.as_ref()?.performance_mode().await.ok()
     }
 
     /// Set performance mode
@@ -112,7 +161,7 @@ impl DaemonClient {
 
     /// Get current GPU mode
     pub async fn get_gpu_mode(&self) -> Option<String> {
-        self.proxy.as_ref()?.get_gpu_mode().await.ok()
+        self.proxy.as_ref()?.gpu_mode().await.ok()
     }
 
     /// Set GPU mode
@@ -126,7 +175,7 @@ impl DaemonClient {
 
     /// Get fan speeds
     pub async fn get_fan_speeds(&self) -> Option<(u32, u32)> {
-        let json = self.proxy.as_ref()?.get_fan_speeds().await.ok()?;
+        let json = self.proxy.as_ref()?.fan_speeds().await.ok()?;
         let v: serde_json::Value = serde_json::from_str(&json).ok()?;
         let cpu = v["cpu"].as_u64()? as u32;
         let gpu = v["gpu"].as_u64()? as u32;
@@ -135,7 +184,7 @@ impl DaemonClient {
 
     /// Get temperatures
     pub async fn get_temperatures(&self) -> Option<(f32, f32)> {
-        let json = self.proxy.as_ref()?.get_temperatures().await.ok()?;
+        let json = self.proxy.as_ref()?.temperatures().await.ok()?;
         let v: serde_json::Value = serde_json::from_str(&json).ok()?;
         let cpu = v["cpu"].as_f64()? as f32;
         let gpu = v["gpu"].as_f64()? as f32;
@@ -172,6 +221,38 @@ impl DaemonClient {
         }
     }
 
+    /// Get fine-grained battery charge-control settings
+    pub async fn get_battery_settings(&self) -> Option<BatterySettings> {
+        let json = self.proxy.as_ref()?.get_battery_settings().await.ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Set fine-grained battery charge-control settings
+    pub async fn set_battery_settings(&self, settings: &BatterySettings) -> bool {
+        if let Some(proxy) = &self.proxy {
+            if let Ok(json) = serde_json::to_string(settings) {
+                return proxy.set_battery_settings(&json).await.unwrap_or(false);
+            }
+        }
+        false
+    }
+
+    /// Get current sustained/boost power limits (TDP)
+    pub async fn get_tdp(&self) -> Option<TdpSettings> {
+        let json = self.proxy.as_ref()?.get_tdp().await.ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    /// Set sustained/boost power limits (TDP)
+    pub async fn set_tdp(&self, settings: &TdpSettings) -> bool {
+        if let Some(proxy) = &self.proxy {
+            if let Ok(json) = serde_json::to_string(settings) {
+                return proxy.set_tdp(&json).await.unwrap_or(false);
+            }
+        }
+        false
+    }
+
     /// List profiles
     pub async fn list_profiles(&self) -> Vec<String> {
         if let Some(proxy) = &self.proxy {
@@ -189,6 +270,14 @@ impl DaemonClient {
         self.proxy.as_ref()?.get_current_profile().await.ok()
     }
 
+    /// Get a summary of the daemon's most recent profile load pass, so the
+    /// UI can tell the user if any profile files were migrated or quarantined
+    pub async fn profile_load_summary(&self) -> Option<ProfileLoadSummary> {
+        let proxy = self.proxy.as_ref()?;
+        let json = proxy.profile_load_summary().await.ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
     /// Apply profile by name
     pub async fn apply_profile(&self, name: &str) -> bool {
         if let Some(proxy) = &self.proxy {
@@ -197,6 +286,140 @@ impl DaemonClient {
             false
         }
     }
+
+    /// List a profile's named variants
+    pub async fn list_variants(&self, profile_name: &str) -> Vec<VariantInfo> {
+        if let Some(proxy) = &self.proxy {
+            if let Ok(json) = proxy.list_variants(profile_name).await {
+                if let Ok(variants) = serde_json::from_str(&json) {
+                    return variants;
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    /// Apply one of a profile's variants by id
+    pub async fn apply_variant(&self, profile_name: &str, variant_id: u64) -> bool {
+        if let Some(proxy) = &self.proxy {
+            proxy.apply_variant(profile_name, variant_id).await.unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Add or replace a variant on a profile
+    pub async fn save_variant(&self, profile_name: &str, variant: &ProfileVariant) -> bool {
+        if let Some(proxy) = &self.proxy {
+            if let Ok(json) = serde_json::to_string(variant) {
+                return proxy.save_variant(profile_name, &json).await.unwrap_or(false);
+            }
+        }
+        false
+    }
+
+    /// Start the daemon's sensor-logging subsystem
+    pub async fn start_monitoring(&self) -> bool {
+        if let Some(proxy) = &self.proxy {
+            proxy.start_monitoring().await.unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Stop the daemon's sensor-logging subsystem
+    pub async fn stop_monitoring(&self) -> bool {
+        if let Some(proxy) = &self.proxy {
+            proxy.stop_monitoring().await.unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Whether the daemon's sensor-logging subsystem is currently running
+    pub async fn is_monitoring(&self) -> bool {
+        if let Some(proxy) = &self.proxy {
+            proxy.is_monitoring().await.unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Recent sampled telemetry, oldest first, for graphing recent history
+    pub async fn monitoring_snapshot(&self) -> Vec<MonitorSample> {
+        if let Some(proxy) = &self.proxy {
+            if let Ok(json) = proxy.monitoring_snapshot().await {
+                if let Ok(samples) = serde_json::from_str(&json) {
+                    return samples;
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    /// Whether the auto-switch policy engine is enabled
+    pub async fn auto_switch_enabled(&self) -> bool {
+        if let Some(proxy) = &self.proxy {
+            proxy.auto_switch_enabled().await.unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Enable or disable the auto-switch policy engine
+    pub async fn set_auto_switch_enabled(&self, enabled: bool) -> bool {
+        if let Some(proxy) = &self.proxy {
+            proxy.set_auto_switch_enabled(enabled).await.unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// List the auto-switch rules, in evaluation order
+    pub async fn list_auto_switch_rules(&self) -> Vec<AutoSwitchRule> {
+        if let Some(proxy) = &self.proxy {
+            if let Ok(json) = proxy.list_auto_switch_rules().await {
+                if let Ok(rules) = serde_json::from_str(&json) {
+                    return rules;
+                }
+            }
+        }
+        Vec::new()
+    }
+
+    /// Add a new auto-switch rule; returns the new rule's id, or `None` on failure
+    pub async fn add_auto_switch_rule(&self, condition: &AutoSwitchCondition, profile_name: &str) -> Option<u64> {
+        let proxy = self.proxy.as_ref()?;
+        let json = serde_json::to_string(condition).ok()?;
+        let id = proxy.add_auto_switch_rule(&json, profile_name).await.ok()?;
+        (id != 0).then_some(id)
+    }
+
+    /// Remove an auto-switch rule by id
+    pub async fn remove_auto_switch_rule(&self, id: u64) -> bool {
+        if let Some(proxy) = &self.proxy {
+            proxy.remove_auto_switch_rule(id).await.unwrap_or(false)
+        } else {
+            false
+        }
+    }
+
+    /// Unpin the active profile, letting the auto-switch policy engine
+    /// resume switching on its next evaluation
+    pub async fn unpin_profile(&self) {
+        if let Some(proxy) = &self.proxy {
+            let _ = proxy.unpin_profile().await;
+        }
+    }
+
+    /// Whether the active profile is currently pinned against auto-switching
+    pub async fn is_profile_pinned(&self) -> bool {
+        if let Some(proxy) = &self.proxy {
+            proxy.is_profile_pinned().await.unwrap_or(false)
+        } else {
+            false
+        }
+    }
 }
 
 impl Default for DaemonClient {
@@ -1,12 +1,22 @@
 //! Fan curve editor widget
 
-use gtk4::{glib, prelude::*, DrawingArea};
+use gtk4::{glib, prelude::*, DrawingArea, GestureClick, GestureDrag};
 use asus_armoury_common::{FanCurve, FanCurvePoint};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Padding (in pixels) around the plotted graph area
+const PADDING: f64 = 40.0;
+/// How close (in pixels) a click needs to land to grab a control point
+const HIT_RADIUS: f64 = 10.0;
+/// Hardware limit on the number of curve points (matches the fan-curve sysfs interface)
+const MAX_POINTS: usize = 8;
 
 /// Widget for editing fan curves
 pub struct FanCurveWidget {
     drawing_area: DrawingArea,
-    curve: FanCurve,
+    curve: Rc<RefCell<FanCurve>>,
+    on_change: Rc<RefCell<Option<Box<dyn Fn(&FanCurve)>>>>,
 }
 
 impl FanCurveWidget {
@@ -14,27 +24,31 @@ impl FanCurveWidget {
         let drawing_area = DrawingArea::new();
         drawing_area.set_content_width(400);
         drawing_area.set_content_height(300);
+        drawing_area.set_focusable(true);
 
-        let curve = FanCurve::default();
+        let curve = Rc::new(RefCell::new(FanCurve::default()));
+        let on_change: Rc<RefCell<Option<Box<dyn Fn(&FanCurve)>>>> = Rc::new(RefCell::new(None));
 
         let widget = Self {
             drawing_area,
             curve,
+            on_change,
         };
 
         widget.setup_drawing();
+        widget.setup_gestures();
         widget
     }
 
     fn setup_drawing(&self) {
         let curve = self.curve.clone();
-        
+
         self.drawing_area.set_draw_func(move |_area, cr, width, height| {
             let width = width as f64;
             let height = height as f64;
-            let padding = 40.0;
-            let graph_width = width - 2.0 * padding;
-            let graph_height = height - 2.0 * padding;
+            let graph_width = width - 2.0 * PADDING;
+            let graph_height = height - 2.0 * PADDING;
+            let curve = curve.borrow();
 
             // Background
             cr.set_source_rgb(0.15, 0.15, 0.15);
@@ -46,16 +60,16 @@ impl FanCurveWidget {
 
             // Vertical grid lines (temperature)
             for i in 0..=10 {
-                let x = padding + (i as f64 / 10.0) * graph_width;
-                cr.move_to(x, padding);
-                cr.line_to(x, height - padding);
+                let x = PADDING + (i as f64 / 10.0) * graph_width;
+                cr.move_to(x, PADDING);
+                cr.line_to(x, height - PADDING);
             }
 
             // Horizontal grid lines (fan %)
             for i in 0..=10 {
-                let y = padding + (i as f64 / 10.0) * graph_height;
-                cr.move_to(padding, y);
-                cr.line_to(width - padding, y);
+                let y = PADDING + (i as f64 / 10.0) * graph_height;
+                cr.move_to(PADDING, y);
+                cr.line_to(width - PADDING, y);
             }
             let _ = cr.stroke();
 
@@ -66,13 +80,11 @@ impl FanCurveWidget {
             let points = &curve.points;
             if !points.is_empty() {
                 let first = &points[0];
-                let x = padding + (first.temperature as f64 / 100.0) * graph_width;
-                let y = height - padding - (first.fan_percent as f64 / 100.0) * graph_height;
+                let (x, y) = Self::point_to_pixel(first, graph_width, graph_height, height);
                 cr.move_to(x, y);
 
                 for point in points.iter().skip(1) {
-                    let x = padding + (point.temperature as f64 / 100.0) * graph_width;
-                    let y = height - padding - (point.fan_percent as f64 / 100.0) * graph_height;
+                    let (x, y) = Self::point_to_pixel(point, graph_width, graph_height, height);
                     cr.line_to(x, y);
                 }
                 let _ = cr.stroke();
@@ -80,8 +92,7 @@ impl FanCurveWidget {
                 // Draw points
                 cr.set_source_rgb(1.0, 1.0, 1.0);
                 for point in points {
-                    let x = padding + (point.temperature as f64 / 100.0) * graph_width;
-                    let y = height - padding - (point.fan_percent as f64 / 100.0) * graph_height;
+                    let (x, y) = Self::point_to_pixel(point, graph_width, graph_height, height);
                     cr.arc(x, y, 5.0, 0.0, 2.0 * std::f64::consts::PI);
                     let _ = cr.fill();
                 }
@@ -95,36 +106,228 @@ impl FanCurveWidget {
             // X-axis labels (temperature)
             for i in 0..=5 {
                 let temp = i * 20;
-                let x = padding + (temp as f64 / 100.0) * graph_width;
+                let x = PADDING + (temp as f64 / 100.0) * graph_width;
                 let text = format!("{}°C", temp);
                 let extents = cr.text_extents(&text).unwrap();
-                cr.move_to(x - extents.width() / 2.0, height - padding + 15.0);
+                cr.move_to(x - extents.width() / 2.0, height - PADDING + 15.0);
                 let _ = cr.show_text(&text);
             }
 
             // Y-axis labels (fan %)
             for i in 0..=5 {
                 let percent = i * 20;
-                let y = height - padding - (percent as f64 / 100.0) * graph_height;
+                let y = height - PADDING - (percent as f64 / 100.0) * graph_height;
                 let text = format!("{}%", percent);
                 let extents = cr.text_extents(&text).unwrap();
-                cr.move_to(padding - extents.width() - 5.0, y + extents.height() / 2.0);
+                cr.move_to(PADDING - extents.width() - 5.0, y + extents.height() / 2.0);
                 let _ = cr.show_text(&text);
             }
         });
     }
 
+    /// Wire up dragging of existing points and double-click add/remove
+    fn setup_gestures(&self) {
+        let dragging: Rc<RefCell<Option<usize>>> = Rc::new(RefCell::new(None));
+
+        let drag = GestureDrag::new();
+
+        {
+            let curve_rc = self.curve.clone();
+            let dragging = dragging.clone();
+            let area = self.drawing_area.clone();
+            drag.connect_drag_begin(move |gesture, x, y| {
+                let width = area.width() as f64 - 2.0 * PADDING;
+                let height = area.height() as f64;
+                let graph_height = height - 2.0 * PADDING;
+                let index = Self::nearest_point_index(&curve_rc.borrow(), x, y, width, graph_height, height);
+                *dragging.borrow_mut() = index;
+                if index.is_none() {
+                    gesture.set_state(gtk4::EventSequenceState::Denied);
+                }
+            });
+        }
+
+        {
+            let curve_rc = self.curve.clone();
+            let dragging = dragging.clone();
+            let on_change = self.on_change.clone();
+            let area = self.drawing_area.clone();
+            drag.connect_drag_update(move |gesture, offset_x, offset_y| {
+                let Some(index) = *dragging.borrow() else { return };
+                let Some((start_x, start_y)) = gesture.start_point() else { return };
+
+                let width = area.width() as f64 - 2.0 * PADDING;
+                let height = area.height() as f64;
+                let graph_height = height - 2.0 * PADDING;
+
+                let x = start_x + offset_x;
+                let y = start_y + offset_y;
+
+                let mut curve = curve_rc.borrow_mut();
+                let (min_temp, max_temp) = Self::neighbor_bounds(&curve.points, index);
+                let temp = Self::pixel_to_temp(x, width).clamp(min_temp as f32, max_temp as f32);
+                let percent = Self::pixel_to_percent(y, height, graph_height);
+
+                curve.points[index].temperature = temp.round() as u8;
+                curve.points[index].fan_percent = percent.round() as u8;
+                let snapshot = curve.clone();
+
+                drop(curve);
+                area.queue_draw();
+                if let Some(cb) = on_change.borrow().as_ref() {
+                    cb(&snapshot);
+                }
+            });
+        }
+
+        {
+            let curve_rc = self.curve.clone();
+            let dragging = dragging.clone();
+            let on_change = self.on_change.clone();
+            drag.connect_drag_end(move |_gesture, _dx, _dy| {
+                if dragging.borrow_mut().take().is_some() {
+                    Self::normalize(&mut curve_rc.borrow_mut());
+                    if let Some(cb) = on_change.borrow().as_ref() {
+                        cb(&curve_rc.borrow());
+                    }
+                }
+            });
+        }
+
+        self.drawing_area.add_controller(drag);
+
+        let click = GestureClick::new();
+        click.set_button(0); // listen to all buttons so we can branch on left/right
+        {
+            let curve_rc = self.curve.clone();
+            let on_change = self.on_change.clone();
+            let area = self.drawing_area.clone();
+            click.connect_pressed(move |gesture, n_press, x, y| {
+                if n_press != 2 {
+                    return;
+                }
+
+                let width = area.width() as f64 - 2.0 * PADDING;
+                let height = area.height() as f64;
+                let graph_height = height - 2.0 * PADDING;
+                let button = gesture.current_button();
+
+                let mut curve = curve_rc.borrow_mut();
+                let mut changed = false;
+
+                if button == 3 {
+                    // Right double-click: remove nearest point (keep at least 2)
+                    if curve.points.len() > 2 {
+                        if let Some(index) =
+                            Self::nearest_point_index(&curve, x, y, width, graph_height, height)
+                        {
+                            curve.points.remove(index);
+                            changed = true;
+                        }
+                    }
+                } else if curve.points.len() < MAX_POINTS {
+                    // Left double-click: add a point at the clicked position
+                    let temp = Self::pixel_to_temp(x, width).round() as u8;
+                    let percent = Self::pixel_to_percent(y, height, graph_height).round() as u8;
+                    curve.points.push(FanCurvePoint { temperature: temp, fan_percent: percent });
+                    changed = true;
+                }
+                if changed {
+                    Self::normalize(&mut curve);
+                }
+                let snapshot = curve.clone();
+
+                if changed {
+                    drop(curve);
+                    area.queue_draw();
+                    if let Some(cb) = on_change.borrow().as_ref() {
+                        cb(&snapshot);
+                    }
+                }
+            });
+        }
+        self.drawing_area.add_controller(click);
+    }
+
+    fn point_to_pixel(point: &FanCurvePoint, graph_width: f64, graph_height: f64, height: f64) -> (f64, f64) {
+        let x = PADDING + (point.temperature as f64 / 100.0) * graph_width;
+        let y = height - PADDING - (point.fan_percent as f64 / 100.0) * graph_height;
+        (x, y)
+    }
+
+    fn pixel_to_temp(x: f64, graph_width: f64) -> f32 {
+        (((x - PADDING) / graph_width) * 100.0).clamp(0.0, 100.0) as f32
+    }
+
+    fn pixel_to_percent(y: f64, height: f64, graph_height: f64) -> f32 {
+        (((height - PADDING - y) / graph_height) * 100.0).clamp(0.0, 100.0) as f32
+    }
+
+    /// The temperature bounds a point may move within, clamped by its immediate
+    /// neighbors so the curve stays monotonic in temperature
+    fn neighbor_bounds(points: &[FanCurvePoint], index: usize) -> (u8, u8) {
+        let min = if index == 0 { 0 } else { points[index - 1].temperature };
+        let max = if index + 1 >= points.len() { 100 } else { points[index + 1].temperature };
+        (min, max)
+    }
+
+    fn nearest_point_index(
+        curve: &FanCurve,
+        x: f64,
+        y: f64,
+        graph_width: f64,
+        graph_height: f64,
+        height: f64,
+    ) -> Option<usize> {
+        curve
+            .points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| {
+                let (px, py) = Self::point_to_pixel(p, graph_width, graph_height, height);
+                (i, ((px - x).powi(2) + (py - y).powi(2)).sqrt())
+            })
+            .filter(|(_, dist)| *dist <= HIT_RADIUS)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+            .map(|(i, _)| i)
+    }
+
     pub fn get_widget(&self) -> &DrawingArea {
         &self.drawing_area
     }
 
-    pub fn set_curve(&mut self, curve: FanCurve) {
-        self.curve = curve;
+    pub fn set_curve(&self, mut curve: FanCurve) {
+        Self::normalize(&mut curve);
+        *self.curve.borrow_mut() = curve;
         self.drawing_area.queue_draw();
     }
 
-    pub fn get_curve(&self) -> &FanCurve {
-        &self.curve
+    /// Enforce the invariants the hwmon fan-curve interface requires: points
+    /// sorted and strictly increasing in temperature, `fan_percent` monotonic
+    /// non-decreasing, and both fields clamped to 0-100
+    fn normalize(curve: &mut FanCurve) {
+        curve.points.sort_by_key(|p| p.temperature);
+        for point in &mut curve.points {
+            point.temperature = point.temperature.min(100);
+            point.fan_percent = point.fan_percent.min(100);
+        }
+        for i in 1..curve.points.len() {
+            if curve.points[i].temperature <= curve.points[i - 1].temperature {
+                curve.points[i].temperature = (curve.points[i - 1].temperature + 1).min(100);
+            }
+            if curve.points[i].fan_percent < curve.points[i - 1].fan_percent {
+                curve.points[i].fan_percent = curve.points[i - 1].fan_percent;
+            }
+        }
+    }
+
+    pub fn get_curve(&self) -> FanCurve {
+        self.curve.borrow().clone()
+    }
+
+    /// Register a callback invoked with the new curve whenever it changes
+    pub fn connect_changed<F: Fn(&FanCurve) + 'static>(&self, f: F) {
+        *self.on_change.borrow_mut() = Some(Box::new(f));
     }
 }
 
@@ -1,20 +1,64 @@
 //! RGB color picker widget
 
-use gtk4::{glib, prelude::*, Box, ColorButton, Label, Orientation, Scale};
-use asus_armoury_common::{RgbColor, RgbEffect, RgbSettings};
+use gtk4::{
+    glib, prelude::*, Box, Button, CheckButton, ColorButton, DropDown, Label, Orientation, Scale,
+    SpinButton,
+};
+use asus_armoury_common::{builtin_palettes, RangeLimit, RgbColor, RgbEffect, RgbSettings, TempSensor};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Keyboards this app targets top out at around this many independently
+/// addressable zones. A palette is cycled out to this length so it spreads
+/// across whatever the real controller reports; `aura_hid::set_rgb` only
+/// writes up to its own discovered zone count and ignores the rest
+const PALETTE_ZONE_SPREAD: usize = 8;
 
 /// Widget for selecting RGB colors and effects
 pub struct RgbPicker {
     container: Box,
     color_button: ColorButton,
+    secondary_color_button: ColorButton,
     brightness_scale: Scale,
     speed_scale: Scale,
+    palette_dropdown: DropDown,
+    /// Whether the current hardware exposes independently-addressable
+    /// per-key zones (`HardwareCapabilities::per_key_rgb`), set by the
+    /// window once capabilities are detected
+    per_key_rgb: Rc<Cell<bool>>,
+    /// Palette colors cycled across `PALETTE_ZONE_SPREAD` zones, set by
+    /// `setup_palette_selection` when a built-in palette is chosen on
+    /// per-key-capable hardware; `None` for "Custom" or non-per-key hardware
+    palette_zone_colors: Rc<RefCell<Option<Vec<RgbColor>>>>,
+    /// Whether `RgbEffect::Temperature` should use `gradient_rows_box`'s
+    /// stops instead of the plain `color`/`color_secondary` band
+    gradient_enabled: CheckButton,
+    gradient_sensor_dropdown: DropDown,
+    /// One row per gradient stop, each a temperature `SpinButton` followed
+    /// by a `ColorButton`; read directly (no separate Rust-side state) the
+    /// same way `get_color` reads `color_button` back
+    gradient_rows_box: Box,
 }
 
 impl RgbPicker {
     pub fn new() -> Self {
         let container = Box::new(Orientation::Vertical, 12);
 
+        // Palette picker
+        let palette_box = Box::new(Orientation::Horizontal, 8);
+        let palette_label = Label::new(Some("Palette"));
+        let builtins = builtin_palettes();
+        let palette_names: Vec<String> = std::iter::once("Custom".to_string())
+            .chain(builtins.iter().map(|p| p.name.clone()))
+            .collect();
+        let palette_name_refs: Vec<&str> = palette_names.iter().map(String::as_str).collect();
+        let palette_dropdown = DropDown::from_strings(&palette_name_refs);
+        palette_dropdown.set_selected(0); // Custom
+        palette_dropdown.set_hexpand(true);
+        palette_box.append(&palette_label);
+        palette_box.append(&palette_dropdown);
+        container.append(&palette_box);
+
         // Color picker
         let color_box = Box::new(Orientation::Horizontal, 8);
         let color_label = Label::new(Some("Color"));
@@ -24,6 +68,15 @@ impl RgbPicker {
         color_box.append(&color_button);
         container.append(&color_box);
 
+        // Secondary color picker (used by two-color effects and palettes)
+        let secondary_color_box = Box::new(Orientation::Horizontal, 8);
+        let secondary_color_label = Label::new(Some("Secondary Color"));
+        let secondary_color_button = ColorButton::new();
+        secondary_color_button.set_rgba(&gtk4::gdk::RGBA::new(0.0, 0.0, 1.0, 1.0));
+        secondary_color_box.append(&secondary_color_label);
+        secondary_color_box.append(&secondary_color_button);
+        container.append(&secondary_color_box);
+
         // Brightness slider
         let brightness_box = Box::new(Orientation::Horizontal, 8);
         let brightness_label = Label::new(Some("Brightness"));
@@ -44,12 +97,200 @@ impl RgbPicker {
         speed_box.append(&speed_scale);
         container.append(&speed_box);
 
-        Self {
+        // Temperature gradient editor: stops drive `RgbEffect::Temperature`
+        // in the daemon's poll loop when enabled, taking precedence there
+        // over the plain color/color_secondary band
+        let gradient_enabled = CheckButton::with_label("Use temperature gradient");
+        container.append(&gradient_enabled);
+
+        let gradient_sensor_box = Box::new(Orientation::Horizontal, 8);
+        let gradient_sensor_label = Label::new(Some("Gradient Sensor"));
+        let gradient_sensor_dropdown = DropDown::from_strings(&["CPU", "GPU", "Max"]);
+        gradient_sensor_dropdown.set_hexpand(true);
+        gradient_sensor_box.append(&gradient_sensor_label);
+        gradient_sensor_box.append(&gradient_sensor_dropdown);
+        container.append(&gradient_sensor_box);
+
+        let gradient_rows_box = Box::new(Orientation::Vertical, 4);
+        container.append(&gradient_rows_box);
+        Self::add_gradient_stop(&gradient_rows_box, 40, RgbColor::new(0, 0, 255));
+        Self::add_gradient_stop(&gradient_rows_box, 65, RgbColor::new(0, 255, 0));
+        Self::add_gradient_stop(&gradient_rows_box, 85, RgbColor::new(255, 0, 0));
+
+        let add_stop_button = Button::with_label("+ Add Stop");
+        {
+            let gradient_rows_box = gradient_rows_box.clone();
+            add_stop_button.connect_clicked(move |_| {
+                Self::add_gradient_stop(&gradient_rows_box, 60, RgbColor::new(255, 255, 0));
+            });
+        }
+        container.append(&add_stop_button);
+
+        let picker = Self {
             container,
             color_button,
+            secondary_color_button,
             brightness_scale,
             speed_scale,
+            palette_dropdown,
+            per_key_rgb: Rc::new(Cell::new(false)),
+            palette_zone_colors: Rc::new(RefCell::new(None)),
+            gradient_enabled,
+            gradient_sensor_dropdown,
+            gradient_rows_box,
+        };
+        picker.setup_palette_selection();
+        picker
+    }
+
+    /// Whether to treat the current hardware as having independently
+    /// addressable per-key zones; selecting a palette afterwards spreads
+    /// it across `zone_colors` instead of just setting primary/secondary
+    pub fn set_per_key_rgb(&self, enabled: bool) {
+        self.per_key_rgb.set(enabled);
+    }
+
+    /// Applying a palette fills primary/secondary from its first two colors,
+    /// and on per-key-capable hardware also cycles the full palette across
+    /// `PALETTE_ZONE_SPREAD` zones into `palette_zone_colors`. Picking
+    /// "Custom" (index 0) leaves the current colors alone and clears the
+    /// per-zone spread
+    fn setup_palette_selection(&self) {
+        let color_button = self.color_button.clone();
+        let secondary_color_button = self.secondary_color_button.clone();
+        let per_key_rgb = self.per_key_rgb.clone();
+        let palette_zone_colors = self.palette_zone_colors.clone();
+        self.palette_dropdown.connect_selected_notify(move |dropdown| {
+            let index = dropdown.selected();
+            if index == 0 {
+                *palette_zone_colors.borrow_mut() = None;
+                return;
+            }
+            let Some(palette) = builtin_palettes().into_iter().nth(index as usize - 1) else {
+                return;
+            };
+            if let Some(primary) = palette.colors.first() {
+                Self::apply_color(&color_button, primary);
+            }
+            if let Some(secondary) = palette.colors.get(1) {
+                Self::apply_color(&secondary_color_button, secondary);
+            }
+
+            *palette_zone_colors.borrow_mut() = if per_key_rgb.get() && !palette.colors.is_empty() {
+                Some(
+                    (0..PALETTE_ZONE_SPREAD)
+                        .map(|i| palette.colors[i % palette.colors.len()])
+                        .collect(),
+                )
+            } else {
+                None
+            };
+        });
+    }
+
+    fn apply_color(button: &ColorButton, color: &RgbColor) {
+        let rgba = gtk4::gdk::RGBA::new(
+            color.r as f32 / 255.0,
+            color.g as f32 / 255.0,
+            color.b as f32 / 255.0,
+            1.0,
+        );
+        button.set_rgba(&rgba);
+    }
+
+    /// Appends an editable (temperature, color) row to the gradient stop list
+    fn add_gradient_stop(rows_box: &Box, temp: u8, color: RgbColor) {
+        let row = Box::new(Orientation::Horizontal, 8);
+
+        let temp_spin = SpinButton::with_range(0.0, 120.0, 1.0);
+        temp_spin.set_value(temp as f64);
+        row.append(&temp_spin);
+
+        let color_button = ColorButton::new();
+        Self::apply_color(&color_button, &color);
+        row.append(&color_button);
+
+        let remove_button = Button::with_label("Remove");
+        {
+            let rows_box = rows_box.clone();
+            let row = row.clone();
+            remove_button.connect_clicked(move |_| {
+                rows_box.remove(&row);
+            });
         }
+        row.append(&remove_button);
+
+        rows_box.append(&row);
+    }
+
+    /// Reads the gradient stop list back from `gradient_rows_box`'s rows,
+    /// sorted ascending by temperature; `None` when disabled or empty
+    fn get_temp_gradient(&self) -> Option<Vec<(u8, RgbColor)>> {
+        if !self.gradient_enabled.is_active() {
+            return None;
+        }
+
+        let mut stops = Vec::new();
+        let mut child = self.gradient_rows_box.first_child();
+        while let Some(row) = child {
+            child = row.next_sibling();
+
+            let Some(temp_spin) = row.first_child().and_then(|w| w.downcast::<SpinButton>().ok())
+            else {
+                continue;
+            };
+            let Some(color_button) = temp_spin
+                .next_sibling()
+                .and_then(|w| w.downcast::<ColorButton>().ok())
+            else {
+                continue;
+            };
+
+            let rgba = color_button.rgba();
+            stops.push((
+                temp_spin.value() as u8,
+                RgbColor {
+                    r: (rgba.red() * 255.0) as u8,
+                    g: (rgba.green() * 255.0) as u8,
+                    b: (rgba.blue() * 255.0) as u8,
+                },
+            ));
+        }
+
+        stops.sort_by_key(|(temp, _)| *temp);
+        if stops.is_empty() {
+            None
+        } else {
+            Some(stops)
+        }
+    }
+
+    /// Replaces the gradient row editor's contents with the given stops
+    fn set_temp_gradient(&self, stops: &[(u8, RgbColor)]) {
+        while let Some(child) = self.gradient_rows_box.first_child() {
+            self.gradient_rows_box.remove(&child);
+        }
+        for &(temp, color) in stops {
+            Self::add_gradient_stop(&self.gradient_rows_box, temp, color);
+        }
+    }
+
+    fn get_temp_sensor(&self) -> Option<TempSensor> {
+        match self.gradient_sensor_dropdown.selected() {
+            0 => Some(TempSensor::Cpu),
+            1 => Some(TempSensor::Gpu),
+            2 => Some(TempSensor::Max),
+            _ => None,
+        }
+    }
+
+    fn set_temp_sensor(&self, sensor: Option<TempSensor>) {
+        let index = match sensor {
+            Some(TempSensor::Gpu) => 1,
+            Some(TempSensor::Max) => 2,
+            Some(TempSensor::Cpu) | None => 0,
+        };
+        self.gradient_sensor_dropdown.set_selected(index);
     }
 
     pub fn get_widget(&self) -> &Box {
@@ -66,13 +307,20 @@ impl RgbPicker {
     }
 
     pub fn set_color(&self, color: &RgbColor) {
-        let rgba = gtk4::gdk::RGBA::new(
-            color.r as f32 / 255.0,
-            color.g as f32 / 255.0,
-            color.b as f32 / 255.0,
-            1.0,
-        );
-        self.color_button.set_rgba(&rgba);
+        Self::apply_color(&self.color_button, color);
+    }
+
+    pub fn get_secondary_color(&self) -> RgbColor {
+        let rgba = self.secondary_color_button.rgba();
+        RgbColor {
+            r: (rgba.red() * 255.0) as u8,
+            g: (rgba.green() * 255.0) as u8,
+            b: (rgba.blue() * 255.0) as u8,
+        }
+    }
+
+    pub fn set_secondary_color(&self, color: &RgbColor) {
+        Self::apply_color(&self.secondary_color_button, color);
     }
 
     pub fn get_brightness(&self) -> u8 {
@@ -91,20 +339,43 @@ impl RgbPicker {
         self.speed_scale.set_value(speed as f64);
     }
 
+    /// Reconfigures the brightness/speed sliders' ranges from this model's
+    /// `SettingsLimits::rgb_brightness`/`rgb_speed`, so they can't be dragged
+    /// past what `HardwareController::set_rgb_settings` will actually accept
+    pub fn apply_rgb_limits(&self, brightness: RangeLimit, speed: RangeLimit) {
+        self.brightness_scale.set_range(brightness.min as f64, brightness.max as f64);
+        self.brightness_scale.set_increments(brightness.step as f64, brightness.step as f64);
+        self.speed_scale.set_range(speed.min as f64, speed.max as f64);
+        self.speed_scale.set_increments(speed.step as f64, speed.step as f64);
+    }
+
     pub fn get_settings(&self, effect: RgbEffect) -> RgbSettings {
         RgbSettings {
             effect,
             color: self.get_color(),
-            color_secondary: None,
+            color_secondary: Some(self.get_secondary_color()),
             brightness: self.get_brightness(),
             speed: self.get_speed(),
+            temp_sensor: self.get_temp_sensor(),
+            temp_band: None,
+            temp_gradient: self.get_temp_gradient(),
+            zone_colors: self.palette_zone_colors.borrow().clone(),
+            temp_poll_interval_ms: None,
         }
     }
 
     pub fn set_settings(&self, settings: &RgbSettings) {
         self.set_color(&settings.color);
+        if let Some(secondary) = settings.color_secondary {
+            self.set_secondary_color(&secondary);
+        }
         self.set_brightness(settings.brightness);
         self.set_speed(settings.speed);
+        self.set_temp_sensor(settings.temp_sensor);
+        self.gradient_enabled.set_active(settings.temp_gradient.is_some());
+        if let Some(stops) = &settings.temp_gradient {
+            self.set_temp_gradient(stops);
+        }
     }
 }
 
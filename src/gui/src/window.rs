@@ -31,12 +31,12 @@ impl MainWindow {
         // Create header bar
         let header = adw::HeaderBar::new();
         
-        // Add profile selector to header
-        let profile_dropdown = gtk4::DropDown::from_strings(&[
-            "Gaming", "Work", "Silent", "Balanced"
-        ]);
+        // Add profile selector to header; placeholder until the real saved
+        // set loads in from the daemon
+        let profile_dropdown = gtk4::DropDown::from_strings(&["Balanced"]);
         profile_dropdown.set_tooltip_text(Some("Select Profile"));
         header.pack_start(&profile_dropdown);
+        Self::populate_profile_list(client.clone(), profile_dropdown);
 
         // Create navigation view with pages
         let nav_view = adw::NavigationView::new();
@@ -53,7 +53,7 @@ impl MainWindow {
         split_view.set_sidebar(Some(&sidebar_page));
         
         // Create main content
-        let content = Self::create_content();
+        let content = Self::create_content(client.clone());
         let content_page = adw::NavigationPage::builder()
             .title("Dashboard")
             .child(&content)
@@ -89,6 +89,28 @@ impl MainWindow {
         window_obj
     }
 
+    /// Fetch the daemon's saved profile set and rebuild the header dropdown
+    /// around it, in place of the hardcoded Gaming/Work/Silent/Balanced list
+    fn populate_profile_list(client: Arc<Mutex<DaemonClient>>, dropdown: gtk4::DropDown) {
+        glib::MainContext::default().spawn_local(async move {
+            let client_guard = client.lock().await;
+            let profiles = client_guard.list_profiles().await;
+            let current = client_guard.get_current_profile().await;
+            drop(client_guard);
+
+            if profiles.is_empty() {
+                return;
+            }
+            let profile_refs: Vec<&str> = profiles.iter().map(String::as_str).collect();
+            dropdown.set_model(Some(&gtk4::StringList::new(&profile_refs)));
+
+            let selected = current
+                .and_then(|name| profiles.iter().position(|p| *p == name))
+                .unwrap_or(0);
+            dropdown.set_selected(selected as u32);
+        });
+    }
+
     fn create_sidebar() -> gtk4::Widget {
         let list_box = gtk4::ListBox::new();
         list_box.set_selection_mode(gtk4::SelectionMode::Single);
@@ -136,7 +158,7 @@ impl MainWindow {
         row
     }
 
-    fn create_content() -> gtk4::Widget {
+    fn create_content(client: Arc<Mutex<DaemonClient>>) -> gtk4::Widget {
         let scroll = gtk4::ScrolledWindow::new();
         scroll.set_policy(gtk4::PolicyType::Never, gtk4::PolicyType::Automatic);
 
@@ -156,7 +178,7 @@ impl MainWindow {
         content_box.append(&Self::create_status_section());
 
         // Quick actions
-        content_box.append(&Self::create_quick_actions());
+        content_box.append(&Self::create_quick_actions(client));
 
         scroll.set_child(Some(&content_box));
         scroll.upcast()
@@ -242,7 +264,7 @@ impl MainWindow {
         card.upcast()
     }
 
-    fn create_quick_actions() -> gtk4::Widget {
+    fn create_quick_actions(client: Arc<Mutex<DaemonClient>>) -> gtk4::Widget {
         let group = adw::PreferencesGroup::new();
         group.set_title("Quick Actions");
 
@@ -255,6 +277,7 @@ impl MainWindow {
         ])));
         perf_row.set_selected(1); // Balanced
         group.add(&perf_row);
+        Self::populate_performance_modes(client.clone(), perf_row);
 
         // GPU mode row
         let gpu_row = adw::ComboRow::new();
@@ -266,15 +289,15 @@ impl MainWindow {
         gpu_row.set_selected(1); // Hybrid
         group.add(&gpu_row);
 
-        // Battery limit row
+        // Battery limit row; populated from the detected model's limits once
+        // connected, in place of a fixed 60/80/100 stand-in
         let battery_row = adw::ComboRow::new();
         battery_row.set_title("Battery Charge Limit");
         battery_row.set_subtitle("Maximum battery charge percentage");
-        battery_row.set_model(Some(&gtk4::StringList::new(&[
-            "60%", "80%", "100%"
-        ])));
-        battery_row.set_selected(2); // 100%
+        battery_row.set_model(Some(&gtk4::StringList::new(&["100%"])));
+        battery_row.set_selected(0);
         group.add(&battery_row);
+        Self::populate_battery_limits(client, battery_row);
 
         // RGB toggle
         let rgb_row = adw::SwitchRow::new();
@@ -285,6 +308,64 @@ impl MainWindow {
 
         group.upcast()
     }
+
+    /// Fetch the firmware's actual `platform_profile_choices` and rebuild the
+    /// combo's options around them, so a mode the hardware can't honor isn't
+    /// offered in the first place. "Manual" has no firmware equivalent and
+    /// always stays available.
+    fn populate_performance_modes(client: Arc<Mutex<DaemonClient>>, perf_row: adw::ComboRow) {
+        glib::MainContext::default().spawn_local(async move {
+            let client_guard = client.lock().await;
+            let Some(caps) = client_guard.get_capabilities().await else {
+                return;
+            };
+            drop(client_guard);
+
+            if caps.available_performance_modes.is_empty() {
+                return;
+            }
+
+            let mut labels: Vec<&str> = caps
+                .available_performance_modes
+                .iter()
+                .map(|mode| match mode {
+                    asus_armoury_common::PerformanceMode::Silent => "Silent",
+                    asus_armoury_common::PerformanceMode::Balanced => "Balanced",
+                    asus_armoury_common::PerformanceMode::Turbo => "Turbo",
+                    asus_armoury_common::PerformanceMode::Manual => "Manual",
+                })
+                .collect();
+            labels.push("Manual");
+
+            let selected = labels.iter().position(|&l| l == "Balanced").unwrap_or(0);
+            perf_row.set_model(Some(&gtk4::StringList::new(&labels)));
+            perf_row.set_selected(selected as u32);
+        });
+    }
+
+    /// Fetch the detected model's `battery_thresholds` and rebuild the combo's
+    /// options around them, selecting the highest as the default
+    fn populate_battery_limits(client: Arc<Mutex<DaemonClient>>, battery_row: adw::ComboRow) {
+        glib::MainContext::default().spawn_local(async move {
+            let client_guard = client.lock().await;
+            let Some(limits) = client_guard.get_limits().await else {
+                return;
+            };
+            drop(client_guard);
+
+            let labels: Vec<String> = limits
+                .battery_thresholds
+                .iter()
+                .map(|pct| format!("{}%", pct))
+                .collect();
+            if labels.is_empty() {
+                return;
+            }
+            let label_refs: Vec<&str> = labels.iter().map(String::as_str).collect();
+            battery_row.set_model(Some(&gtk4::StringList::new(&label_refs)));
+            battery_row.set_selected(label_refs.len() as u32 - 1);
+        });
+    }
     
     fn start_status_updates(client: Arc<Mutex<DaemonClient>>, _window: adw::ApplicationWindow) {
         // Schedule periodic updates every 2 seconds
@@ -298,12 +379,46 @@ impl MainWindow {
                         // TODO: Update UI widgets with new status
                         // This would require storing references to the UI widgets
                     }
+
+                    Self::update_temperature_rgb(&client_guard).await;
                 }
             });
             glib::ControlFlow::Continue
         });
     }
 
+    /// When `RgbEffect::Temperature` is active, recompute the keyboard color
+    /// from the live CPU/GPU temperature and push it to the daemon
+    async fn update_temperature_rgb(client: &DaemonClient) {
+        use asus_armoury_common::{temperature_to_color, RgbEffect, TempSensor};
+
+        let Some(mut settings) = client.get_rgb_settings().await else {
+            return;
+        };
+        if settings.effect != RgbEffect::Temperature {
+            return;
+        }
+        if settings.temp_gradient.is_some() || settings.color_secondary.is_some() {
+            // The daemon's own poll loop drives multi-stop gradients and the
+            // color/color_secondary linear band; this hue-based fallback only
+            // applies when neither is configured
+            return;
+        }
+
+        let Some((cpu_temp, gpu_temp)) = client.get_temperatures().await else {
+            return;
+        };
+        let sensor_temp = match settings.temp_sensor.unwrap_or(TempSensor::Cpu) {
+            TempSensor::Cpu => cpu_temp,
+            TempSensor::Gpu => gpu_temp,
+            TempSensor::Max => cpu_temp.max(gpu_temp),
+        };
+        let (t_cold, t_hot) = settings.temp_band.unwrap_or((40, 90));
+
+        settings.color = temperature_to_color(sensor_temp, t_cold as f32, t_hot as f32);
+        client.set_rgb_settings(&settings).await;
+    }
+
     pub fn present(&self) {
         self.window.present();
     }
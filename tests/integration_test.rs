@@ -82,14 +82,14 @@ fn test_system_status_default() {
 
 #[test]
 fn test_battery_settings_valid_limits() {
-    let settings = BatterySettings { charge_limit: 60 };
-    assert_eq!(settings.charge_limit, 60);
-    
-    let settings = BatterySettings { charge_limit: 80 };
-    assert_eq!(settings.charge_limit, 80);
-    
-    let settings = BatterySettings { charge_limit: 100 };
-    assert_eq!(settings.charge_limit, 100);
+    let settings = BatterySettings { charge_control_end_threshold: 60, ..Default::default() };
+    assert_eq!(settings.charge_control_end_threshold, 60);
+
+    let settings = BatterySettings { charge_control_end_threshold: 80, ..Default::default() };
+    assert_eq!(settings.charge_control_end_threshold, 80);
+
+    let settings = BatterySettings { charge_control_end_threshold: 100, ..Default::default() };
+    assert_eq!(settings.charge_control_end_threshold, 100);
 }
 
 #[test]
@@ -137,20 +137,20 @@ mod profile_tests {
             performance_mode: PerformanceMode::Turbo,
             gpu_mode: GpuMode::Dedicated,
             fan_mode: FanMode::Auto,
-            fan_curve: None,
             rgb_settings: RgbSettings {
                 effect: RgbEffect::Rainbow,
                 color: RgbColor::new(255, 0, 0),
-                color_secondary: None,
                 brightness: 100,
                 speed: 75,
+                ..Default::default()
             },
-            battery_settings: BatterySettings { charge_limit: 100 },
+            battery_settings: BatterySettings { charge_control_end_threshold: 100, ..Default::default() },
+            ..Default::default()
         };
-        
+
         assert_eq!(profile.performance_mode, PerformanceMode::Turbo);
         assert_eq!(profile.gpu_mode, GpuMode::Dedicated);
-        assert_eq!(profile.battery_settings.charge_limit, 100);
+        assert_eq!(profile.battery_settings.charge_control_end_threshold, 100);
     }
 
     #[test]
@@ -160,20 +160,20 @@ mod profile_tests {
             performance_mode: PerformanceMode::Silent,
             gpu_mode: GpuMode::Integrated,
             fan_mode: FanMode::Auto,
-            fan_curve: None,
             rgb_settings: RgbSettings {
                 effect: RgbEffect::Off,
                 color: RgbColor::default(),
-                color_secondary: None,
                 brightness: 0,
                 speed: 0,
+                ..Default::default()
             },
-            battery_settings: BatterySettings { charge_limit: 60 },
+            battery_settings: BatterySettings { charge_control_end_threshold: 60, ..Default::default() },
+            ..Default::default()
         };
-        
+
         assert_eq!(profile.performance_mode, PerformanceMode::Silent);
         assert_eq!(profile.gpu_mode, GpuMode::Integrated);
-        assert_eq!(profile.battery_settings.charge_limit, 60);
+        assert_eq!(profile.battery_settings.charge_control_end_threshold, 60);
         assert_eq!(profile.rgb_settings.effect, RgbEffect::Off);
     }
 }